@@ -0,0 +1,49 @@
+use crate::spec_files::{self, FileErrors, ObjectIndex};
+use crate::{LoadOptions, Object, Registry, Source};
+use std::path::PathBuf;
+
+/** Load all spec files with `std::fs` (without an async runtime). The files are loaded one after
+    another, but the objects are collected the same way as by `spec_files::load`.
+*/
+pub(crate) fn load(
+    directories: &[PathBuf],
+    options: &LoadOptions,
+) -> anyhow::Result<(Vec<Object>, FileErrors)> {
+    let mut index = ObjectIndex::new(options.directory_precedence);
+    for (directory_index, directory) in directories.iter().enumerate() {
+        let paths = spec_files::spec_files_in(directory, options, &|| false)?;
+        for (file_index, path) in paths.into_iter().enumerate() {
+            let result = spec_files::read_spec_file(&path, options);
+            index.add_result(directory_index, file_index, path, result);
+        }
+    }
+    Ok(index.into_parts())
+}
+
+impl Registry {
+    /** Initialize a registry with a number of given directories without an async runtime.
+        The spec files are read with `std::fs`, the result is the same as with `init`.
+    */
+    pub fn init_blocking(directories: Vec<PathBuf>) -> anyhow::Result<Registry> {
+        let options = LoadOptions::default();
+        let (objects, errors) = load(&directories, &options)?;
+        let mut reg = Registry::from_parts(directories, options, objects);
+        reg.record_load_errors(&errors);
+        Ok(reg)
+    }
+
+    /** Reload all files without an async runtime. See `reload`.
+        Only registries that were loaded from directories or from memory can be reloaded this way.
+    */
+    pub fn reload_blocking(&mut self) -> anyhow::Result<()> {
+        let (objects, errors) = match &self.source {
+            Source::Directories(directories) => load(directories, &self.options)?,
+            // there's nothing to load again, so the objects are kept
+            Source::InMemory => return Ok(()),
+            _ => anyhow::bail!("Blocking reload is only supported for directories"),
+        };
+        self.record_load_errors(&errors);
+        self.replace_objects(objects);
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+/// A single link of a CoRE Link Format (RFC 6690) value, e.g. `</3/0>;rt="oma.lwm2m"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreLink {
+    /// The target URI of the link (without the enclosing `<` and `>`).
+    pub uri: String,
+    /// The link parameters in the order they appear. Parameters without a value have `None`.
+    pub params: Vec<(String, Option<String>)>,
+}
+
+impl CoreLink {
+    /// Get the value of a link parameter by name.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// Get the resource type (`rt`) parameter of the link.
+    pub fn resource_type(&self) -> Option<&str> {
+        self.param("rt")
+    }
+}
+
+/// Error indicating that a value is not well-formed CoRE Link Format.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseCoreLinkError {
+    msg: String,
+}
+
+impl ParseCoreLinkError {
+    fn new(s: &str) -> Self {
+        Self {
+            msg: format!("Could not parse CoRE link: {}", s),
+        }
+    }
+}
+
+/** Parse a CoRE Link Format value into its links.
+    The value is a comma separated list of links of the form `<uri>;name=value;name="quoted"`.
+    An empty (or blank) value yields an empty list.
+*/
+pub fn parse_corelink(value: &str) -> Result<Vec<CoreLink>, ParseCoreLinkError> {
+    let mut links = Vec::new();
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Ok(links);
+    }
+
+    loop {
+        let (link, remainder) = parse_link(rest).ok_or_else(|| ParseCoreLinkError::new(value))?;
+        links.push(link);
+        rest = remainder.trim_start();
+        if rest.is_empty() {
+            return Ok(links);
+        }
+        rest = rest
+            .strip_prefix(',')
+            .ok_or_else(|| ParseCoreLinkError::new(value))?
+            .trim_start();
+    }
+}
+
+fn parse_link(s: &str) -> Option<(CoreLink, &str)> {
+    let s = s.strip_prefix('<')?;
+    let end = s.find('>')?;
+    let uri = s[..end].to_string();
+    let mut rest = &s[end + 1..];
+    let mut params = Vec::new();
+
+    while let Some(param) = rest.trim_start().strip_prefix(';') {
+        let param = param.trim_start();
+        let name_len = param
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "!#$&+-.^_`|~*".contains(c)))
+            .unwrap_or(param.len());
+        if name_len == 0 {
+            return None;
+        }
+        let name = param[..name_len].to_string();
+        rest = &param[name_len..];
+
+        match rest.strip_prefix('=') {
+            Some(value) => {
+                let (value, remainder) = parse_param_value(value)?;
+                params.push((name, Some(value)));
+                rest = remainder;
+            }
+            None => params.push((name, None)),
+        }
+    }
+
+    Some((CoreLink { uri, params }, rest))
+}
+
+fn parse_param_value(s: &str) -> Option<(String, &str)> {
+    if let Some(quoted) = s.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        Some((quoted[..end].to_string(), &quoted[end + 1..]))
+    } else {
+        let end = s.find([';', ',']).unwrap_or(s.len());
+        let value = s[..end].trim();
+        if value.is_empty() {
+            return None;
+        }
+        Some((value.to_string(), &s[end..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::corelink::{parse_corelink, CoreLink};
+
+    #[test]
+    fn test_parse_corelink() {
+        let links = parse_corelink(r#"</3/0>;rt="oma.lwm2m";ct=11543, </1/0>;obs"#).unwrap();
+        assert_eq!(
+            links,
+            vec![
+                CoreLink {
+                    uri: "/3/0".to_string(),
+                    params: vec![
+                        ("rt".to_string(), Some("oma.lwm2m".to_string())),
+                        ("ct".to_string(), Some("11543".to_string())),
+                    ],
+                },
+                CoreLink {
+                    uri: "/1/0".to_string(),
+                    params: vec![("obs".to_string(), None)],
+                },
+            ]
+        );
+        assert_eq!(links[0].resource_type(), Some("oma.lwm2m"));
+    }
+
+    #[test]
+    fn test_parse_corelink_empty() {
+        assert_eq!(parse_corelink(" "), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_corelink_malformed() {
+        assert!(parse_corelink("/3/0;rt=\"oma.lwm2m\"").is_err());
+        assert!(parse_corelink("</3/0;rt=x").is_err());
+        assert!(parse_corelink("</3/0>;rt=\"unterminated").is_err());
+        assert!(parse_corelink("</3/0> </1/0>").is_err());
+    }
+}
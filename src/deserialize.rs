@@ -19,6 +19,38 @@ where
     }
 }
 
+/// Empty (or whitespace only) elements are treated as absent.
+pub(crate) fn deserialize_optional_version<'de, D>(
+    deserializer: D,
+) -> Result<Option<Version>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    if s.trim().is_empty() {
+        return Ok(None);
+    }
+    match Version::from_str(&s) {
+        Ok(v) => Ok(Some(v)),
+        _ => Err(D::Error::invalid_value(
+            Unexpected::Str(&s),
+            &"a valid version string",
+        )),
+    }
+}
+
+/// Parse boolean-like tokens (`true`/`false`/`1`/`0`) that some converted spec files use.
+fn parse_bool_token(s: &str) -> Option<bool> {
+    match s {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ if s.eq_ignore_ascii_case("true") => Some(true),
+        _ if s.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    }
+}
+
+/// Accepts `Multiple`/`Single` as well as `true`/`false`/`1`/`0`.
 pub(crate) fn deserialize_multiple_instances<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
@@ -28,10 +60,13 @@ where
     match s.as_str() {
         "Multiple" => Ok(true),
         "Single" => Ok(false),
-        _ => Err(Error::unknown_variant(&s, &["Multiple", "Single"])),
+        _ => parse_bool_token(&s).ok_or_else(|| {
+            Error::unknown_variant(&s, &["Multiple", "Single", "true", "false", "1", "0"])
+        }),
     }
 }
 
+/// Accepts `Mandatory`/`Optional` as well as `true`/`false`/`1`/`0`.
 pub(crate) fn deserialize_mandatory<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
@@ -41,7 +76,9 @@ where
     match s.as_str() {
         "Mandatory" => Ok(true),
         "Optional" => Ok(false),
-        _ => Err(Error::unknown_variant(&s, &["Mandatory", "Optional"])),
+        _ => parse_bool_token(&s).ok_or_else(|| {
+            Error::unknown_variant(&s, &["Mandatory", "Optional", "true", "false", "1", "0"])
+        }),
     }
 }
 
@@ -61,19 +98,47 @@ where
     Ok(Resources::deserialize(deserializer)?.item)
 }
 
+/// Empty (or whitespace only) elements are treated as absent.
+pub(crate) fn deserialize_optional_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+
+    let s = s.trim();
+    if s.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(s.to_string()))
+    }
+}
+
+/** Each letter (`R`, `W`, `E`) grants an operation, so also combinations like `RWE` or `WE` are accepted.
+    An empty value means that no operations are allowed. Other values are rejected.
+*/
 pub(crate) fn deserialize_operations<'de, D>(deserializer: D) -> Result<Operations, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = Deserialize::deserialize(deserializer)?;
+    parse_operations(&s)
+        .ok_or_else(|| Error::unknown_variant(&s, &["", "R", "W", "RW", "E", "RE", "WE", "RWE"]))
+}
 
-    match s.as_str() {
-        "R" => Ok(Operations::Read),
-        "W" => Ok(Operations::Write),
-        "RW" => Ok(Operations::ReadWrite),
-        "E" => Ok(Operations::Execute),
-        _ => Ok(Operations::None),
+/// Parse an operations token (e.g. `RW`). Returns `None` for tokens with unknown letters.
+pub(crate) fn parse_operations(s: &str) -> Option<Operations> {
+    let mut bits = 0;
+    for c in s.trim().chars() {
+        bits |= match c {
+            'R' => 0b100,
+            'W' => 0b010,
+            'E' => 0b001,
+            _ => return None,
+        };
     }
+    Some(Operations::from_bits(bits))
 }
 
 pub(crate) fn deserialize_resource_type<'de, D>(deserializer: D) -> Result<ResourceType, D::Error>
@@ -82,16 +147,148 @@ where
 {
     let s: String = Deserialize::deserialize(deserializer)?;
 
-    match s.as_str() {
-        "String" => Ok(ResourceType::String),
-        "Integer" => Ok(ResourceType::Integer),
-        "Float" => Ok(ResourceType::Float),
-        "Boolean" => Ok(ResourceType::Boolean),
-        "Opaque" => Ok(ResourceType::Opaque),
-        "Time" => Ok(ResourceType::Time),
-        "Objlnk" => Ok(ResourceType::ObjectLink),
-        "Unsigned Integer" => Ok(ResourceType::UnsignedInteger),
-        "Corelnk" => Ok(ResourceType::Corelink),
-        _ => Ok(ResourceType::Other),
+    // unknown types are tolerated, use `ResourceType::from_str` to reject them
+    Ok(ResourceType::from_str(&s).unwrap_or(ResourceType::Other))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Operations, Resource, ResourceType};
+    use serde_xml_rs::from_str;
+
+    fn parse_item(
+        operations: &str,
+        multiple_instances: &str,
+        mandatory: &str,
+        type_token: &str,
+    ) -> Resource {
+        let xml = format!(
+            r#"<Item ID="0">
+                <Name>Test</Name>
+                <Operations>{}</Operations>
+                <MultipleInstances>{}</MultipleInstances>
+                <Mandatory>{}</Mandatory>
+                <Type>{}</Type>
+            </Item>"#,
+            operations, multiple_instances, mandatory, type_token
+        );
+        from_str(&xml).unwrap()
+    }
+
+    fn parse_type(type_token: &str) -> ResourceType {
+        parse_item("R", "Single", "Optional", type_token).resource_type
+    }
+
+    fn parse_flags(multiple_instances: &str, mandatory: &str) -> (bool, bool) {
+        let res = parse_item("R", multiple_instances, mandatory, "String");
+        (res.has_multiple_instances, res.is_mandatory)
+    }
+
+    fn parse_operations(operations: &str) -> Operations {
+        parse_item(operations, "Single", "Optional", "String").operations
+    }
+
+    #[test]
+    fn test_deserialize_operations_standard() {
+        assert_eq!(parse_operations("R"), Operations::Read);
+        assert_eq!(parse_operations("W"), Operations::Write);
+        assert_eq!(parse_operations("RW"), Operations::ReadWrite);
+        assert_eq!(parse_operations("E"), Operations::Execute);
+        assert_eq!(parse_operations(""), Operations::None);
+    }
+
+    #[test]
+    fn test_deserialize_operations_combinations() {
+        let rwe = parse_operations("RWE");
+        assert_eq!(rwe, Operations::ReadWriteExecute);
+        assert!(rwe.is_read() && rwe.is_write() && rwe.is_execute());
+
+        let we = parse_operations("WE");
+        assert_eq!(we, Operations::WriteExecute);
+        assert!(!we.is_read() && we.is_write() && we.is_execute());
+
+        assert_eq!(parse_operations("RE"), Operations::ReadExecute);
+        assert_eq!(parse_operations("WR"), Operations::ReadWrite);
+    }
+
+    #[test]
+    fn test_deserialize_operations_invalid() {
+        for operations in ["r", "Read", "RX", "-"] {
+            let xml = format!(
+                r#"<Item ID="0">
+                    <Name>Test</Name>
+                    <Operations>{}</Operations>
+                    <MultipleInstances>Single</MultipleInstances>
+                    <Mandatory>Optional</Mandatory>
+                    <Type>String</Type>
+                </Item>"#,
+                operations
+            );
+            assert!(from_str::<Resource>(&xml).is_err(), "{}", operations);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_flags_canonical_words() {
+        assert_eq!(parse_flags("Multiple", "Mandatory"), (true, true));
+        assert_eq!(parse_flags("Single", "Optional"), (false, false));
+    }
+
+    #[test]
+    fn test_deserialize_flags_true_false() {
+        assert_eq!(parse_flags("true", "true"), (true, true));
+        assert_eq!(parse_flags("false", "false"), (false, false));
+        assert_eq!(parse_flags("True", "FALSE"), (true, false));
+    }
+
+    #[test]
+    fn test_deserialize_flags_numbers() {
+        assert_eq!(parse_flags("1", "1"), (true, true));
+        assert_eq!(parse_flags("0", "0"), (false, false));
+    }
+
+    #[test]
+    fn test_deserialize_flags_invalid() {
+        let xml = r#"<Item ID="0">
+                <Name>Test</Name>
+                <Operations>R</Operations>
+                <MultipleInstances>Several</MultipleInstances>
+                <Mandatory>yes</Mandatory>
+                <Type>String</Type>
+            </Item>"#;
+        assert!(from_str::<Resource>(xml).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_resource_type_alternate_spellings() {
+        assert_eq!(parse_type("Corelnk"), ResourceType::Corelink);
+        assert_eq!(parse_type("CoreLink"), ResourceType::Corelink);
+        assert_eq!(parse_type("corelnk"), ResourceType::Corelink);
+        assert_eq!(parse_type("Objlnk"), ResourceType::ObjectLink);
+        assert_eq!(parse_type("ObjLink"), ResourceType::ObjectLink);
+        assert_eq!(
+            parse_type("Unsigned Integer"),
+            ResourceType::UnsignedInteger
+        );
+        assert_eq!(parse_type("UnsignedInteger"), ResourceType::UnsignedInteger);
+        assert_eq!(parse_type("string"), ResourceType::String);
+        assert_eq!(parse_type("BOOLEAN"), ResourceType::Boolean);
+    }
+
+    #[test]
+    fn test_deserialize_resource_type_unknown() {
+        assert_eq!(parse_type("Complex"), ResourceType::Other);
+        assert_eq!(parse_type(""), ResourceType::Other);
+    }
+
+    #[test]
+    fn test_resource_type_from_str_strict() {
+        use std::str::FromStr;
+        assert!(ResourceType::from_str("Complex").is_err());
+        assert_eq!(ResourceType::from_str(""), Ok(ResourceType::Other));
+        assert_eq!(
+            ResourceType::from_str("Objlnk"),
+            Ok(ResourceType::ObjectLink)
+        );
     }
 }
@@ -0,0 +1,245 @@
+use crate::{Object, Operations, Registry, ResourceType, Version};
+
+/// The kind of a change of a resource that breaks clients.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResourceChangeKind {
+    /// The type of the resource changed.
+    TypeChanged {
+        /// The type in the old version.
+        from: ResourceType,
+        /// The type in the new version.
+        to: ResourceType,
+    },
+    /// Some of the operations that were allowed on the resource are not allowed anymore.
+    OperationsNarrowed {
+        /// The operations in the old version.
+        from: Operations,
+        /// The operations in the new version.
+        to: Operations,
+    },
+    /// A mandatory resource was removed.
+    MandatoryRemoved,
+}
+
+/// A breaking change of a resource between two versions of an object.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceChange {
+    /// The ID of the changed resource.
+    pub resource_id: u16,
+    /// The kind of the change.
+    pub kind: ResourceChangeKind,
+}
+
+impl Registry {
+    /** Get the changes of resources between two versions of an object that break clients.
+        Only the following changes are reported: a resource's type changed, the allowed operations
+        of a resource were narrowed (e.g. `RW` to `R`) or a mandatory resource was removed.
+        The result is sorted by resource ID. If one of the versions is not loaded the result is empty.
+    */
+    pub fn breaking_resource_changes(
+        &self,
+        object_id: u16,
+        from: Version,
+        to: Version,
+    ) -> Vec<ResourceChange> {
+        let (Some(old), Some(new)) = (
+            self.get_object_by_id(object_id, from),
+            self.get_object_by_id(object_id, to),
+        ) else {
+            return Vec::new();
+        };
+
+        let mut changes = Vec::new();
+        for old_res in &old.resources {
+            let Some(new_res) = new.resources.iter().find(|r| r.id == old_res.id) else {
+                if old_res.is_mandatory {
+                    changes.push(ResourceChange {
+                        resource_id: old_res.id,
+                        kind: ResourceChangeKind::MandatoryRemoved,
+                    });
+                }
+                continue;
+            };
+
+            if old_res.resource_type != new_res.resource_type {
+                changes.push(ResourceChange {
+                    resource_id: old_res.id,
+                    kind: ResourceChangeKind::TypeChanged {
+                        from: old_res.resource_type,
+                        to: new_res.resource_type,
+                    },
+                });
+            }
+            if !new_res.operations.includes(old_res.operations) {
+                changes.push(ResourceChange {
+                    resource_id: old_res.id,
+                    kind: ResourceChangeKind::OperationsNarrowed {
+                        from: old_res.operations,
+                        to: new_res.operations,
+                    },
+                });
+            }
+        }
+        changes.sort_by_key(|c| c.resource_id);
+        changes
+    }
+
+    /** Get all resources whose type differs between consecutive loaded versions of an object.
+        Each entry contains the resource ID, the older version with its type and the newer version
+        with its type. The result is sorted by version and then by resource ID.
+    */
+    pub fn resource_type_changes(
+        &self,
+        object_id: u16,
+    ) -> Vec<(u16, Version, ResourceType, Version, ResourceType)> {
+        let mut versions: Vec<&Object> = self
+            .objects
+            .iter()
+            .filter(|o| o.object_id == object_id)
+            .collect();
+        versions.sort_by_key(|o| o.object_version);
+
+        let mut changes = Vec::new();
+        for pair in versions.windows(2) {
+            let (old, new) = (pair[0], pair[1]);
+            let mut pair_changes: Vec<_> = old
+                .resources
+                .iter()
+                .filter_map(|old_res| {
+                    let new_res = new.resources.iter().find(|r| r.id == old_res.id)?;
+                    (old_res.resource_type != new_res.resource_type).then_some((
+                        old_res.id,
+                        old.object_version,
+                        old_res.resource_type,
+                        new.object_version,
+                        new_res.resource_type,
+                    ))
+                })
+                .collect();
+            pair_changes.sort_by_key(|c| c.0);
+            changes.extend(pair_changes);
+        }
+        changes
+    }
+}
+
+/// The differences between two definitions of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectDiff {
+    /// The object ID.
+    pub object_id: u16,
+    /// The object version.
+    pub object_version: Version,
+    /// Indicates if the metadata of the object (name, URN, LwM2M versions, flags) changed.
+    pub metadata_changed: bool,
+    /// The IDs of the resources that were added.
+    pub added_resources: Vec<u16>,
+    /// The IDs of the resources that were removed.
+    pub removed_resources: Vec<u16>,
+    /// The IDs of the resources that exist in both definitions but differ (see `Resource::eq_semantic`).
+    pub changed_resources: Vec<u16>,
+}
+
+impl ObjectDiff {
+    /// Check if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        !self.metadata_changed
+            && self.added_resources.is_empty()
+            && self.removed_resources.is_empty()
+            && self.changed_resources.is_empty()
+    }
+}
+
+impl Object {
+    /// Compare this (old) object definition with a new one. All resource ID lists are sorted.
+    pub fn diff(&self, new: &Object) -> ObjectDiff {
+        let metadata_changed = self.name != new.name
+            || self.urn() != new.urn()
+            || self.lwm2m_version != new.lwm2m_version
+            || self.lwm2m_version_max != new.lwm2m_version_max
+            || self.has_multiple_instances != new.has_multiple_instances
+            || self.is_mandatory != new.is_mandatory;
+
+        let mut diff = ObjectDiff {
+            object_id: self.object_id,
+            object_version: self.object_version,
+            metadata_changed,
+            added_resources: Vec::new(),
+            removed_resources: Vec::new(),
+            changed_resources: Vec::new(),
+        };
+
+        for old_res in &self.resources {
+            match new.resources.iter().find(|r| r.id == old_res.id) {
+                Some(new_res) if !new_res.eq_semantic(old_res) => {
+                    diff.changed_resources.push(old_res.id)
+                }
+                Some(_) => {}
+                None => diff.removed_resources.push(old_res.id),
+            }
+        }
+        for new_res in &new.resources {
+            if !self.resources.iter().any(|r| r.id == new_res.id) {
+                diff.added_resources.push(new_res.id);
+            }
+        }
+
+        diff.added_resources.sort();
+        diff.removed_resources.sort();
+        diff.changed_resources.sort();
+        diff
+    }
+}
+
+/// The differences between two registries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryDiff {
+    /// The objects (ID and version) that only exist in the new registry.
+    pub added: Vec<(u16, Version)>,
+    /// The objects (ID and version) that only exist in the old registry.
+    pub removed: Vec<(u16, Version)>,
+    /// The objects that exist in both registries but differ.
+    pub changed: Vec<ObjectDiff>,
+}
+
+/** Compare two registries (e.g. two releases of the spec files).
+    Objects are matched by ID and version. All lists are sorted by object ID and version.
+*/
+pub fn diff_registries(old: &Registry, new: &Registry) -> RegistryDiff {
+    diff_objects(&old.objects, &new.objects)
+}
+
+/// Compare two lists of objects. See `diff_registries`.
+pub(crate) fn diff_objects(old: &[Object], new: &[Object]) -> RegistryDiff {
+    fn find(objects: &[Object], id: u16, version: Version) -> Option<&Object> {
+        objects
+            .iter()
+            .find(|o| o.object_id == id && o.object_version == version)
+    }
+    let mut diff = RegistryDiff::default();
+
+    for old_obj in old {
+        match find(new, old_obj.object_id, old_obj.object_version) {
+            Some(new_obj) => {
+                let object_diff = old_obj.diff(new_obj);
+                if !object_diff.is_empty() {
+                    diff.changed.push(object_diff);
+                }
+            }
+            None => diff
+                .removed
+                .push((old_obj.object_id, old_obj.object_version)),
+        }
+    }
+    for new_obj in new {
+        if find(old, new_obj.object_id, new_obj.object_version).is_none() {
+            diff.added.push((new_obj.object_id, new_obj.object_version));
+        }
+    }
+
+    diff.added.sort();
+    diff.removed.sort();
+    diff.changed
+        .sort_by_key(|d| (d.object_id, d.object_version));
+    diff
+}
@@ -0,0 +1,226 @@
+use crate::Object;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+/// The elements of an object that are defined by the schema.
+pub(crate) const OBJECT_ELEMENTS: &[&str] = &[
+    "Name",
+    "Description1",
+    "Description2",
+    "ObjectID",
+    "ObjectURN",
+    "LWM2MVersion",
+    "LWM2MVersionMax",
+    "ObjectVersion",
+    "MultipleInstances",
+    "Mandatory",
+    "Resources",
+];
+/// The attributes of an object that are defined by the schema.
+pub(crate) const OBJECT_ATTRIBUTES: &[&str] = &["ObjectType"];
+/// The elements of a resource that are defined by the schema.
+pub(crate) const RESOURCE_ELEMENTS: &[&str] = &[
+    "Name",
+    "Operations",
+    "MultipleInstances",
+    "Mandatory",
+    "Type",
+    "RangeEnumeration",
+    "Units",
+    "Description",
+    "Description2",
+];
+/// The attributes of a resource that are defined by the schema (including the `ResourceID` variant).
+pub(crate) const RESOURCE_ATTRIBUTES: &[&str] = &["ID", "ResourceID"];
+
+/// The vendor extensions of an object and of its resources (by resource ID).
+#[derive(Debug, Default)]
+pub(crate) struct Extensions {
+    pub(crate) object: HashMap<String, String>,
+    pub(crate) resources: HashMap<u16, HashMap<String, String>>,
+    /// The names of unknown elements that are not captured because they are not scalar.
+    pub(crate) skipped: Vec<String>,
+}
+
+/// An element that is not defined by the schema and is currently read.
+struct Capture {
+    resource_id: Option<u16>,
+    key: String,
+    value: String,
+    depth: usize,
+    is_scalar: bool,
+}
+
+/** Set the vendor extensions of the objects that were parsed from the given XML.
+    Only simple scalar extensions are captured: attributes and elements that only contain text.
+    If `deny_unknown_fields` is set, any attribute or element that is not defined by the schema
+    results in an error instead.
+*/
+pub(crate) fn apply(
+    xml: &str,
+    objects: &mut [Object],
+    deny_unknown_fields: bool,
+) -> anyhow::Result<()> {
+    for (object, extensions) in objects.iter_mut().zip(collect(xml)?) {
+        assign(object, extensions, deny_unknown_fields)?;
+    }
+    Ok(())
+}
+
+/** Set the vendor extensions of an object and its resources. If `deny_unknown_fields` is set, any
+    extension (captured or skipped) results in an error instead.
+*/
+pub(crate) fn assign(
+    object: &mut Object,
+    mut extensions: Extensions,
+    deny_unknown_fields: bool,
+) -> anyhow::Result<()> {
+    if deny_unknown_fields {
+        let unknown = extensions
+            .object
+            .keys()
+            .chain(extensions.resources.values().flat_map(|r| r.keys()))
+            .chain(&extensions.skipped)
+            .next();
+        if let Some(name) = unknown {
+            anyhow::bail!("Unknown field in object {}: {}", object.object_id, name);
+        }
+    }
+    object.extensions = extensions.object;
+    for resource in &mut object.resources {
+        if let Some(resource_extensions) = extensions.resources.remove(&resource.id) {
+            resource.extensions = resource_extensions;
+        }
+    }
+    Ok(())
+}
+
+/// Collect the vendor extensions of all objects in the order of the objects.
+fn collect(xml: &str) -> anyhow::Result<Vec<Extensions>> {
+    let mut reader = Reader::from_str(xml);
+    let mut all = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut resource_id = None;
+    let mut capture: Option<Capture> = None;
+
+    loop {
+        let event = reader.read_event()?;
+        match &event {
+            Event::Start(e) | Event::Empty(e) => {
+                let name = String::from_utf8(e.local_name().as_ref().to_vec())?;
+                if let Some(capture) = &mut capture {
+                    capture.is_scalar = false;
+                } else {
+                    let parent = path.last().map(String::as_str);
+                    match (parent, name.as_str()) {
+                        (_, "Object") => all.push(Extensions {
+                            object: attributes(e, OBJECT_ATTRIBUTES)?,
+                            ..Extensions::default()
+                        }),
+                        (Some("Resources"), "Item") => {
+                            resource_id = parse_resource_id(e)?;
+                            let extensions = attributes(e, RESOURCE_ATTRIBUTES)?;
+                            if let (Some(id), Some(object)) = (resource_id, all.last_mut()) {
+                                object.resources.insert(id, extensions);
+                            }
+                        }
+                        (Some("Object"), _) if !OBJECT_ELEMENTS.contains(&name.as_str()) => {
+                            capture = Some(Capture::new(None, name.clone(), path.len()));
+                        }
+                        (Some("Item"), _)
+                            if resource_id.is_some()
+                                && !RESOURCE_ELEMENTS.contains(&name.as_str()) =>
+                        {
+                            capture = Some(Capture::new(resource_id, name.clone(), path.len()));
+                        }
+                        _ => {}
+                    }
+                }
+                if matches!(event, Event::Start(_)) {
+                    path.push(name);
+                } else if capture.as_ref().is_some_and(|c| c.depth == path.len()) {
+                    finish(capture.take(), &mut all);
+                }
+            }
+            Event::End(_) => {
+                path.pop();
+                if capture.as_ref().is_some_and(|c| c.depth == path.len()) {
+                    finish(capture.take(), &mut all);
+                }
+            }
+            Event::Text(e) => {
+                if let Some(capture) = &mut capture {
+                    capture.value.push_str(&e.decode()?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some(capture) = &mut capture {
+                    capture.value.push_str(&e.decode()?);
+                }
+            }
+            Event::GeneralRef(e) => {
+                if let Some(capture) = &mut capture {
+                    let reference = format!("&{};", e.decode()?);
+                    capture.value.push_str(&unescape(&reference)?);
+                }
+            }
+            Event::Eof => return Ok(all),
+            _ => {}
+        }
+    }
+}
+
+impl Capture {
+    fn new(resource_id: Option<u16>, key: String, depth: usize) -> Self {
+        Self {
+            resource_id,
+            key,
+            value: String::new(),
+            depth,
+            is_scalar: true,
+        }
+    }
+}
+
+/// Store a captured element if it only contained text.
+fn finish(capture: Option<Capture>, all: &mut [Extensions]) {
+    let (Some(capture), Some(object)) = (capture, all.last_mut()) else {
+        return;
+    };
+    if !capture.is_scalar {
+        object.skipped.push(capture.key);
+        return;
+    }
+    let extensions = match capture.resource_id {
+        Some(id) => object.resources.entry(id).or_default(),
+        None => &mut object.object,
+    };
+    extensions.insert(capture.key, capture.value.trim().to_string());
+}
+
+/// Get the ID of a resource from the attributes of its `Item` element.
+fn parse_resource_id(e: &BytesStart) -> anyhow::Result<Option<u16>> {
+    for attr in e.attributes() {
+        let attr = attr?;
+        if RESOURCE_ATTRIBUTES.contains(&std::str::from_utf8(attr.key.as_ref())?) {
+            return Ok(attr.unescape_value()?.trim().parse().ok());
+        }
+    }
+    Ok(None)
+}
+
+/// Get all attributes of an element except the given known ones.
+fn attributes(e: &BytesStart, known: &[&str]) -> anyhow::Result<HashMap<String, String>> {
+    let mut map = HashMap::new();
+    for attr in e.attributes() {
+        let attr = attr?;
+        let key = std::str::from_utf8(attr.key.as_ref())?;
+        let is_namespace = key == "xmlns" || key.starts_with("xmlns:") || key.starts_with("xsi:");
+        if !is_namespace && !known.contains(&key) {
+            map.insert(key.to_string(), attr.unescape_value()?.into_owned());
+        }
+    }
+    Ok(map)
+}
@@ -0,0 +1,162 @@
+use crate::extensions::{
+    self, Extensions, OBJECT_ATTRIBUTES, OBJECT_ELEMENTS, RESOURCE_ATTRIBUTES, RESOURCE_ELEMENTS,
+};
+use crate::LwM2MSpec;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The fields that contain an ID. They are deserialized as numbers.
+const ID_FIELDS: &[&str] = &["ObjectID", "ID", "ResourceID"];
+
+/** Parse a spec file in JSON. It has the same structure and field names as the XML spec files:
+    ```json
+    {
+      "LWM2M": {
+        "Object": [{
+          "Name": "Device", "ObjectID": 3, "ObjectVersion": "1.1", "LWM2MVersion": "1.1",
+          "MultipleInstances": "Single", "Mandatory": true,
+          "Resources": [
+            { "ID": 0, "Name": "Manufacturer", "Operations": "R", "MultipleInstances": false,
+              "Mandatory": "Optional", "Type": "String" }
+          ]
+        }]
+      }
+    }
+    ```
+    The root element (`LWM2M`) can be omitted. A single object doesn't need to be wrapped in an
+    array and the resources can be listed directly (without `Item`).
+    Scalars can be given as strings (like in XML), as booleans or as numbers. Versions should be
+    strings because numbers can't represent versions like `1.10`.
+    Fields of objects and resources that are not defined by the schema are captured as vendor
+    extensions if they are scalars (see `Object::extensions`). If `deny_unknown_fields` is set,
+    they result in an error instead.
+*/
+pub(crate) fn parse(json: &str, deny_unknown_fields: bool) -> anyhow::Result<LwM2MSpec> {
+    let mut root: Value = serde_json::from_str(json)?;
+    if let Some(spec) = root.get_mut("LWM2M") {
+        root = spec.take();
+    }
+    let mut all_extensions = Vec::new();
+    if let Some(objects) = root.get_mut("Object") {
+        wrap_in_array(objects);
+        for object in objects.as_array_mut().into_iter().flatten() {
+            normalize_object(object);
+            all_extensions.push(take_extensions(object));
+        }
+    }
+    let mut spec: LwM2MSpec = serde_json::from_value(root)?;
+    for (object, extensions) in spec.objects.iter_mut().zip(all_extensions) {
+        extensions::assign(object, extensions, deny_unknown_fields)?;
+    }
+    Ok(spec)
+}
+
+/// Remove the fields that are not defined by the schema from a (normalized) object and its resources.
+fn take_extensions(object: &mut Value) -> Extensions {
+    let mut extensions = Extensions::default();
+    let Some(fields) = object.as_object_mut() else {
+        return extensions;
+    };
+    extensions.object = take_unknown(
+        fields,
+        &[OBJECT_ELEMENTS, OBJECT_ATTRIBUTES],
+        &mut extensions.skipped,
+    );
+
+    let items = fields
+        .get_mut("Resources")
+        .and_then(|r| r.get_mut("Item"))
+        .and_then(Value::as_array_mut);
+    for item in items.into_iter().flatten() {
+        let Some(fields) = item.as_object_mut() else {
+            continue;
+        };
+        let id = RESOURCE_ATTRIBUTES
+            .iter()
+            .find_map(|key| fields.get(*key).and_then(Value::as_u64))
+            .and_then(|id| u16::try_from(id).ok());
+        let unknown = take_unknown(
+            fields,
+            &[RESOURCE_ELEMENTS, RESOURCE_ATTRIBUTES],
+            &mut extensions.skipped,
+        );
+        if let Some(id) = id {
+            extensions.resources.insert(id, unknown);
+        }
+    }
+    extensions
+}
+
+/** Remove the fields that are not in any of the known lists. Scalars are returned, the names of all
+    other fields are added to `skipped`.
+*/
+fn take_unknown(
+    fields: &mut Map<String, Value>,
+    known: &[&[&str]],
+    skipped: &mut Vec<String>,
+) -> HashMap<String, String> {
+    let mut unknown = HashMap::new();
+    fields.retain(|key, value| {
+        if known.iter().any(|k| k.contains(&key.as_str())) {
+            return true;
+        }
+        match value {
+            Value::String(s) => {
+                unknown.insert(key.clone(), s.trim().to_string());
+            }
+            _ => skipped.push(key.clone()),
+        }
+        false
+    });
+    unknown
+}
+
+fn normalize_object(object: &mut Value) {
+    let Some(fields) = object.as_object_mut() else {
+        return;
+    };
+    normalize_scalars(fields);
+
+    if let Some(resources) = fields.get_mut("Resources") {
+        if resources.is_array() {
+            *resources = Value::Object(Map::from_iter([("Item".to_string(), resources.take())]));
+        }
+        if let Some(items) = resources.get_mut("Item") {
+            wrap_in_array(items);
+            for item in items.as_array_mut().into_iter().flatten() {
+                if let Some(fields) = item.as_object_mut() {
+                    normalize_scalars(fields);
+                }
+            }
+        }
+    }
+}
+
+/** Convert the scalars to the representation the deserializers of the XML spec files expect:
+    IDs are numbers and all other scalars are strings (e.g. `true` or `1.1`). Fields that are
+    `null` are removed.
+*/
+fn normalize_scalars(fields: &mut Map<String, Value>) {
+    // absent fields get their default value
+    fields.retain(|_, value| !value.is_null());
+    for (key, value) in fields.iter_mut() {
+        let normalized = match value {
+            Value::String(s) if ID_FIELDS.contains(&key.as_str()) => {
+                match s.trim().parse::<u16>() {
+                    Ok(id) => Value::from(id),
+                    Err(_) => continue,
+                }
+            }
+            Value::Bool(b) if !ID_FIELDS.contains(&key.as_str()) => Value::String(b.to_string()),
+            Value::Number(n) if !ID_FIELDS.contains(&key.as_str()) => Value::String(n.to_string()),
+            _ => continue,
+        };
+        *value = normalized;
+    }
+}
+
+fn wrap_in_array(value: &mut Value) {
+    if !value.is_array() {
+        *value = Value::Array(vec![value.take()]);
+    }
+}
@@ -14,31 +14,137 @@
 //! assert!(registry.has_object_id(3, Version::new(1, 1)));
 //! # })
 //! ```
+#[cfg(feature = "blocking")]
+mod blocking;
+mod corelink;
 mod deserialize;
+mod diff;
+mod extensions;
+mod json;
+mod manifest;
+#[cfg(feature = "docs")]
+mod markdown;
+pub mod path;
+mod query;
+mod reusable;
+mod senml;
+mod serialize;
+#[cfg(feature = "snapshot")]
+mod snapshot;
 mod spec_files;
+mod streaming;
+mod tree;
+mod validate;
+
+pub use corelink::{parse_corelink, CoreLink, ParseCoreLinkError};
+pub use diff::{diff_registries, ObjectDiff, RegistryDiff, ResourceChange, ResourceChangeKind};
+pub use query::RegistryQuery;
+pub use reusable::REUSABLE_RESOURCE_IDS;
+#[cfg(feature = "snapshot")]
+pub use snapshot::SnapshotError;
+pub use streaming::ObjectStream;
+pub use tree::{ObjectNode, ResourceNode};
+pub use validate::ValidationIssue;
 
 use deserialize::deserialize_mandatory;
 use deserialize::deserialize_multiple_instances;
 use deserialize::deserialize_operations;
+use deserialize::deserialize_optional_string;
+use deserialize::deserialize_optional_version;
 use deserialize::deserialize_resource_type;
 use deserialize::deserialize_unwrap_resources_list;
 use deserialize::deserialize_version;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::num::ParseIntError;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use tokio::io::AsyncRead;
 
-/// This can represent a LwM2M version or an object version.
+/** This can represent a LwM2M version or an object version.
+    Versions are ordered by major, minor and patch version.
+    Note that the packed representation (`to_packed`) doesn't contain the patch version, so versions
+    with a patch version don't survive a round trip through `to_packed` and `from_packed`.
+*/
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Version {
     major: u16,
     minor: u16,
+    patch: u16,
 }
 
 impl Version {
-    /// Create a new Version with a minor and mayor version number.
+    /// Create a new Version with a minor and mayor version number. The patch version is 0.
     pub fn new(major: u16, minor: u16) -> Self {
-        Self { major, minor }
+        Self::with_patch(major, minor, 0)
+    }
+
+    /// Create a new Version with a mayor, minor and patch version number (e.g. `1.0.1`).
+    pub fn with_patch(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Get the major version.
+    pub fn major(&self) -> u16 {
+        self.major
+    }
+
+    /// Get the minor version.
+    pub fn minor(&self) -> u16 {
+        self.minor
+    }
+
+    /// Get the patch version (0 if the version doesn't have one).
+    pub fn patch(&self) -> u16 {
+        self.patch
+    }
+
+    /** Check if this version is compatible with another version.
+        Two versions are compatible if they have the same major version. The minor versions can
+        differ (e.g. 1.1 is compatible with 1.2 but not with 2.0).
+    */
+    pub fn is_compatible_with(&self, other: &Version) -> bool {
+        self.major == other.major
+    }
+
+    /** Pack the version into an `u32` with the major version in the high and the minor version in the low 16 bits.
+        Note: The patch version is not part of the packed version, it's lost when packing
+        (e.g. `1.0.1` and `1.0` result in the same value).
+    */
+    pub fn to_packed(&self) -> u32 {
+        (u32::from(self.major) << 16) | u32::from(self.minor)
+    }
+
+    /// Create a version from an `u32` that was created with `to_packed`. The patch version is 0.
+    pub fn from_packed(packed: u32) -> Self {
+        Self::new((packed >> 16) as u16, (packed & 0xffff) as u16)
+    }
+
+    /** Parse a version and tell whether the minor version was stated explicitly.
+        E.g. `1` and `1.0` result in the same (equal) version, but only for `1.0` the minor version
+        is explicit. This allows tooling to preserve the exact form of the source.
+    */
+    pub fn parse_with_minor_explicit(s: &str) -> Result<(Self, bool), ParseVersionError> {
+        let mut numbers = s.trim().split('.');
+        let count = numbers.clone().count();
+        let version = match count {
+            1 => Version::new(Self::parse_digit(numbers.next())?, 0),
+            2 => Version::new(
+                Self::parse_digit(numbers.next())?,
+                Self::parse_digit(numbers.next())?,
+            ),
+            3 => Version::with_patch(
+                Self::parse_digit(numbers.next())?,
+                Self::parse_digit(numbers.next())?,
+                Self::parse_digit(numbers.next())?,
+            ),
+            0 | 4.. => return Err(ParseVersionError::new(s)),
+        };
+        Ok((version, count > 1))
     }
 
     fn parse_digit(num: Option<&str>) -> Result<u16, ParseVersionError> {
@@ -71,28 +177,77 @@ impl From<ParseIntError> for ParseVersionError {
     }
 }
 
+impl Serialize for Version {
+    /// A version is serialized as string in the same form as it's displayed (e.g. `1.1`).
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl std::fmt::Display for Version {
+    /** Format the version in the form `major.minor` (e.g. `1.1`). The patch version is only added
+        if it's not 0 (e.g. `1.0.1`). The result can be parsed again with `Version::from_str`.
+    */
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.patch == 0 {
+            write!(f, "{}.{}", self.major, self.minor)
+        } else {
+            write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
 impl FromStr for Version {
     type Err = ParseVersionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut numbers = s.trim().split('.');
-        let count = numbers.clone().count();
-        match count {
-            1 => Ok(Version {
-                major: Self::parse_digit(numbers.next())?,
-                minor: 0,
-            }),
-            2 => Ok(Version {
-                major: Self::parse_digit(numbers.next())?,
-                minor: Self::parse_digit(numbers.next())?,
-            }),
-            0 | 3.. => Err(Self::Err::new(s)),
+        Self::parse_with_minor_explicit(s).map(|(version, _)| version)
+    }
+}
+
+/// Error indicating that loading the spec files was cancelled.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Loading of spec files was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Error indicating why a spec file could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io(String),
+    /// The content of the file could not be parsed.
+    Parse(String),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(msg) => write!(f, "Could not read spec file: {}", msg),
+            LoadError::Parse(msg) => write!(f, "Could not parse spec file: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<anyhow::Error> for LoadError {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast_ref::<std::io::Error>() {
+            Some(io_error) => LoadError::Io(io_error.to_string()),
+            None => LoadError::Parse(e.to_string()),
         }
     }
 }
 
 /// Operations that are allowed on a resource.
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Operations {
     /// Resource can be only read.
     Read,
@@ -102,12 +257,72 @@ pub enum Operations {
     ReadWrite,
     /// Resource can be only executed.
     Execute,
+    /// Resource can be read and executed (non-standard).
+    ReadExecute,
+    /// Resource can be written and executed (non-standard).
+    WriteExecute,
+    /// Resource can be read, written and executed (non-standard).
+    ReadWriteExecute,
     /// No operations allowed on resource.
     None,
 }
 
+impl Operations {
+    const READ: u8 = 0b100;
+    const WRITE: u8 = 0b010;
+    const EXECUTE: u8 = 0b001;
+
+    /// The allowed operations as bits (read, write, execute).
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            Operations::Read => Self::READ,
+            Operations::Write => Self::WRITE,
+            Operations::ReadWrite => Self::READ | Self::WRITE,
+            Operations::Execute => Self::EXECUTE,
+            Operations::ReadExecute => Self::READ | Self::EXECUTE,
+            Operations::WriteExecute => Self::WRITE | Self::EXECUTE,
+            Operations::ReadWriteExecute => Self::READ | Self::WRITE | Self::EXECUTE,
+            Operations::None => 0,
+        }
+    }
+
+    /// Create the operations from bits (read, write, execute).
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & (Self::READ | Self::WRITE | Self::EXECUTE) {
+            Self::READ => Operations::Read,
+            Self::WRITE => Operations::Write,
+            0b110 => Operations::ReadWrite,
+            Self::EXECUTE => Operations::Execute,
+            0b101 => Operations::ReadExecute,
+            0b011 => Operations::WriteExecute,
+            0b111 => Operations::ReadWriteExecute,
+            _ => Operations::None,
+        }
+    }
+
+    /// Check if the resource can be read.
+    pub fn is_read(self) -> bool {
+        self.bits() & Self::READ != 0
+    }
+
+    /// Check if the resource can be written.
+    pub fn is_write(self) -> bool {
+        self.bits() & Self::WRITE != 0
+    }
+
+    /// Check if the resource can be executed.
+    pub fn is_execute(self) -> bool {
+        self.bits() & Self::EXECUTE != 0
+    }
+
+    /// Check if all operations allowed by `other` are also allowed by `self`.
+    pub(crate) fn includes(self, other: Operations) -> bool {
+        self.bits() & other.bits() == other.bits()
+    }
+}
+
 /// Indicates the type of resource.
-#[derive(Debug, Deserialize, Copy, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ResourceType {
     /// The resource is a string (utf-8).
     String,
@@ -131,11 +346,71 @@ pub enum ResourceType {
     Other,
 }
 
+impl ResourceType {
+    /** Get the name of the Rust type that can hold a value of this resource type (e.g. for code generation).
+        `Time` is represented as a Unix timestamp (`i64`), `ObjectLink` as a tuple of object ID and
+        object instance ID (`(u16, u16)`) and `Corelink` as the unparsed `String`.
+        Resources without a type (`Other`, e.g. executable resources) map to the unit type `()`.
+    */
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            ResourceType::String => "String",
+            ResourceType::Integer => "i64",
+            ResourceType::Float => "f64",
+            ResourceType::Boolean => "bool",
+            ResourceType::Opaque => "Vec<u8>",
+            ResourceType::Time => "i64",
+            ResourceType::ObjectLink => "(u16, u16)",
+            ResourceType::UnsignedInteger => "u64",
+            ResourceType::Corelink => "String",
+            ResourceType::Other => "()",
+        }
+    }
+}
+
+/// Error indicating that a resource type token is not known.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseResourceTypeError {
+    msg: String,
+}
+
+impl FromStr for ResourceType {
+    type Err = ParseResourceTypeError;
+
+    /** Parse a resource type token as used in spec files.
+        The tokens are matched case-insensitively and ignoring whitespace, so alternate spellings
+        like `CoreLink`, `ObjLink` or `UnsignedInteger` are accepted. An empty token (as used for
+        executable resources) results in `ResourceType::Other`, all other unknown tokens are rejected.
+    */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '_')
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match token.as_str() {
+            "string" => Ok(ResourceType::String),
+            "integer" => Ok(ResourceType::Integer),
+            "float" => Ok(ResourceType::Float),
+            "boolean" => Ok(ResourceType::Boolean),
+            "opaque" => Ok(ResourceType::Opaque),
+            "time" => Ok(ResourceType::Time),
+            "objlnk" | "objlink" | "objectlink" => Ok(ResourceType::ObjectLink),
+            "unsignedinteger" => Ok(ResourceType::UnsignedInteger),
+            "corelnk" | "corelink" => Ok(ResourceType::Corelink),
+            "" => Ok(ResourceType::Other),
+            _ => Err(ParseResourceTypeError {
+                msg: format!("Unknown resource type: {}", s),
+            }),
+        }
+    }
+}
+
 /// A resource within an LwM2M object.
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct Resource {
     /// The resource ID.
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", alias = "ResourceID")]
     pub id: u16, // Number of resources is 'unbound' in XSD
     /// The name of the resource.
     #[serde(rename = "Name")]
@@ -155,6 +430,39 @@ pub struct Resource {
     /// The type of the resource.
     #[serde(rename = "Type", deserialize_with = "deserialize_resource_type")]
     pub resource_type: ResourceType,
+    /// The description of the resource (`None` if absent or empty).
+    #[serde(
+        rename = "Description",
+        default,
+        deserialize_with = "deserialize_optional_string"
+    )]
+    pub description: Option<String>,
+    /// The units of the resource, e.g. `s` or `%RH` (`None` if absent or empty).
+    #[serde(
+        rename = "Units",
+        default,
+        deserialize_with = "deserialize_optional_string"
+    )]
+    pub units: Option<String>,
+    /// The allowed range or enumeration of values, e.g. `0..100` or `0,1,2` (`None` if absent or empty).
+    #[serde(
+        rename = "RangeEnumeration",
+        default,
+        deserialize_with = "deserialize_optional_string"
+    )]
+    pub range_enumeration: Option<String>,
+    /// Supplementary notes of the resource from `<Description2>` (`None` if absent or empty).
+    #[serde(
+        rename = "Description2",
+        default,
+        deserialize_with = "deserialize_optional_string"
+    )]
+    pub notes: Option<String>,
+    /** Vendor extensions of the resource: attributes and elements that are not defined by the schema.
+        Only simple scalar extensions (attributes and elements that contain only text) are captured.
+    */
+    #[serde(skip)]
+    pub extensions: HashMap<String, String>,
 }
 
 impl Resource {
@@ -174,8 +482,95 @@ impl Resource {
             has_multiple_instances,
             is_mandatory,
             resource_type,
+            description: None,
+            units: None,
+            range_enumeration: None,
+            notes: None,
+            extensions: HashMap::new(),
         }
     }
+
+    /// Set the description of the resource.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Set the units of the resource.
+    pub fn with_units(mut self, units: &str) -> Self {
+        self.units = Some(units.to_string());
+        self
+    }
+
+    /// Set the allowed range or enumeration of values of the resource.
+    pub fn with_range_enumeration(mut self, range_enumeration: &str) -> Self {
+        self.range_enumeration = Some(range_enumeration.to_string());
+        self
+    }
+
+    /** Compare two resources ignoring cosmetic fields.
+        The ID, name, operations, type, multiple instances and mandatory flags and the
+        range/enumeration (it defines the valid values) are compared.
+        The description, the units and the notes are ignored.
+    */
+    pub fn eq_semantic(&self, other: &Resource) -> bool {
+        self.id == other.id
+            && self.name == other.name
+            && self.operations == other.operations
+            && self.resource_type == other.resource_type
+            && self.has_multiple_instances == other.has_multiple_instances
+            && self.is_mandatory == other.is_mandatory
+            && self.range_enumeration == other.range_enumeration
+    }
+
+    /** Check if a value in plain text representation is valid for the type of the resource.
+        Numeric types need to be parsable as such, booleans need to be `0` or `1`,
+        object links need to be of the form `object_id:instance_id` and CoRE links need to be
+        well-formed CoRE Link Format. All other types accept any value.
+    */
+    pub fn validate_value(&self, value: &str) -> bool {
+        match self.resource_type {
+            ResourceType::Integer | ResourceType::Time => value.parse::<i64>().is_ok(),
+            ResourceType::UnsignedInteger => value.parse::<u64>().is_ok(),
+            ResourceType::Float => value.parse::<f64>().is_ok(),
+            ResourceType::Boolean => value == "0" || value == "1",
+            ResourceType::ObjectLink => match value.split_once(':') {
+                Some((object_id, instance_id)) => {
+                    object_id.parse::<u16>().is_ok() && instance_id.parse::<u16>().is_ok()
+                }
+                None => false,
+            },
+            ResourceType::Corelink => parse_corelink(value).is_ok(),
+            ResourceType::String | ResourceType::Opaque | ResourceType::Other => true,
+        }
+    }
+
+    /** Get the ID of the object an object link resource refers to.
+        The spec files don't define the target explicitly, so it's read from the conventions used
+        by the OMA files: either the range/enumeration is the object ID (e.g. `11`) or the
+        description states it (e.g. `(OMNA registered Object ID:11)`).
+        Returns `None` if the resource isn't an object link or the target isn't documented.
+    */
+    pub fn object_link_target(&self) -> Option<u16> {
+        if self.resource_type != ResourceType::ObjectLink {
+            return None;
+        }
+        if let Some(id) = self
+            .range_enumeration
+            .as_deref()
+            .and_then(|r| r.trim().parse().ok())
+        {
+            return Some(id);
+        }
+        let description = self.description.as_deref()?;
+        let (_, rest) = description.split_once("Object ID")?;
+        let digits: String = rest
+            .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    }
 }
 
 /// Represents a LwM2M object as defined in a specification file
@@ -187,15 +582,28 @@ pub struct Object {
     /// The object ID.
     #[serde(rename = "ObjectID")]
     pub object_id: u16,
-    /// The URN of the object.
-    #[serde(rename = "ObjectURN")]
-    pub object_urn: String,
+    /// The URN of the object as stated in the spec file (some drafts omit it or leave it empty).
+    #[serde(
+        rename = "ObjectURN",
+        default,
+        deserialize_with = "deserialize_optional_string"
+    )]
+    pub object_urn: Option<String>,
     /// The object version
     #[serde(rename = "ObjectVersion", deserialize_with = "deserialize_version")]
     pub object_version: Version,
     /// The LwM2M version where the object was introduced.
     #[serde(rename = "LWM2MVersion", deserialize_with = "deserialize_version")]
     pub lwm2m_version: Version,
+    /** The highest LwM2M version the object supports, for extended schemas that declare a range
+        of supported versions (`<LWM2MVersionMax>`). `None` means that there's no upper bound.
+    */
+    #[serde(
+        rename = "LWM2MVersionMax",
+        default,
+        deserialize_with = "deserialize_optional_version"
+    )]
+    pub lwm2m_version_max: Option<Version>,
     /// Indicates it the object can have multiple instances.
     #[serde(
         rename = "MultipleInstances",
@@ -205,12 +613,94 @@ pub struct Object {
     /// Indicates if the object is mandatory.
     #[serde(rename = "Mandatory", deserialize_with = "deserialize_mandatory")]
     pub is_mandatory: bool,
-    /// The list of resources of the object.
+    /// The list of resources of the object (empty if the object doesn't define any resources).
     #[serde(
         rename = "Resources",
+        default,
         deserialize_with = "deserialize_unwrap_resources_list"
     )]
     pub resources: Vec<Resource>,
+    /// The path of the spec file the object was loaded from (if it was loaded from a file).
+    #[serde(skip)]
+    pub source_path: Option<PathBuf>,
+    /// The version of the XML schema the spec file of the object was authored against (if declared).
+    #[serde(skip)]
+    pub schema_version: Option<String>,
+    /** Vendor extensions of the object: attributes and elements that are not defined by the schema.
+        Only simple scalar extensions (attributes and elements that contain only text) are captured.
+    */
+    #[serde(skip)]
+    pub extensions: HashMap<String, String>,
+}
+
+impl Object {
+    /// Get the URN of the object. If the spec file doesn't state an URN it is derived.
+    pub fn urn(&self) -> String {
+        match &self.object_urn {
+            Some(urn) if !urn.trim().is_empty() => urn.trim().to_string(),
+            _ => self.derived_urn(),
+        }
+    }
+
+    /** Derive a best-effort URN from the object ID and version in the form
+        `urn:oma:lwm2m:{authority}:{id}:{version}`.
+        The authority is `oma` for IDs below 2048, `ext` for IDs up to 10240 and `x` otherwise.
+        The version is omitted for objects with version 1.0.
+    */
+    pub fn derived_urn(&self) -> String {
+        let authority = match self.object_id {
+            0..=2047 => "oma",
+            2048..=10240 => "ext",
+            _ => "x",
+        };
+        let urn = format!("urn:oma:lwm2m:{}:{}", authority, self.object_id);
+        if self.object_version == Version::new(1, 0) {
+            urn
+        } else {
+            format!("{}:{}", urn, self.object_version)
+        }
+    }
+}
+
+/// Selects which version of an object is used if several versions are loaded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VersionSelector {
+    /// Use the object with the highest version.
+    Newest,
+    /// Use only objects with exactly this version.
+    Exact(Version),
+}
+
+/// The expected layout of the resources of an object instance. All lists are sorted by resource ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceLayout {
+    /// The IDs of the mandatory resources (they must be present in an instance).
+    pub mandatory: Vec<u16>,
+    /// The IDs of the optional resources.
+    pub optional: Vec<u16>,
+    /// The IDs of the resources that can have multiple instances (mandatory or optional).
+    pub multi_instance: Vec<u16>,
+}
+
+/// Owned metadata of a resource together with the object it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceInfo {
+    /// The ID of the object the resource belongs to.
+    pub object_id: u16,
+    /// The version of the object the resource belongs to.
+    pub object_version: Version,
+    /// The resource ID.
+    pub resource_id: u16,
+    /// The name of the resource.
+    pub name: String,
+    /// The allowed operations for the resource.
+    pub operations: Operations,
+    /// Indicates if the resource has multiple instances.
+    pub has_multiple_instances: bool,
+    /// Indicates if the resource is mandatory.
+    pub is_mandatory: bool,
+    /// The type of the resource.
+    pub resource_type: ResourceType,
 }
 
 /// Represents a LwM2M object specification file.
@@ -222,39 +712,366 @@ pub struct LwM2MSpec {
     */
     #[serde(rename = "Object")]
     pub objects: Vec<Object>,
+    /// The location of the XML schema the file was authored against (if declared).
+    #[serde(
+        rename = "noNamespaceSchemaLocation",
+        alias = "schemaLocation",
+        default
+    )]
+    pub schema_location: Option<String>,
+}
+
+impl LwM2MSpec {
+    /** Get the version of the XML schema the file was authored against.
+        It's derived from the schema location (e.g. `LWM2M-v1_1.xsd` results in `1.1`).
+        If the location doesn't contain a version the name of the schema file is returned.
+    */
+    pub fn schema_version(&self) -> Option<String> {
+        schema_version_from_location(self.schema_location.as_ref()?)
+    }
+}
+
+/// Defines which definition takes effect if the same object is defined in several directories.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Precedence {
+    /// Keep the definitions of all directories.
+    #[default]
+    KeepAll,
+    /// The definition from the directory that comes first in the list of directories wins.
+    FirstWins,
+    /// The definition from the directory that comes last in the list of directories wins.
+    LastWins,
+}
+
+/// Options that control which files are loaded and how they are processed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadOptions {
+    /** File extensions (without the leading dot) that are considered to be spec files.
+        The extensions are matched case-insensitively. Defaults to `["xml"]`.
+        Add `json` to also load JSON spec files: files with the extension `json` are parsed as
+        JSON spec files.
+    */
+    pub extensions: Vec<String>,
+    /** Sort the resources of each object by their ID after parsing. Defaults to `true`.
+        Note that this changes the order of the resources relative to the source file.
+    */
+    pub sort_resources: bool,
+    /** Defines which definition is kept if the same object (ID and version) is defined in
+        several directories. Defaults to keeping all definitions.
+    */
+    pub directory_precedence: Precedence,
+    /** Files that are bigger than this size (in bytes) are parsed object by object with an
+        `ObjectStream` instead of reading the whole file into memory. Defaults to `None`
+        (streaming is disabled). This only applies to XML spec files, JSON spec files are always
+        read into memory.
+    */
+    pub streaming_threshold: Option<u64>,
+    /** Walk the directory of the manifest if the manifest doesn't exist when initializing with
+        `Registry::init_from_manifest`. Defaults to `true`.
+    */
+    pub manifest_fallback: bool,
+    /** Names of files that are skipped when walking the directories (e.g. deprecated spec files).
+        A name is either matched exactly or as a simple glob pattern where `*` matches any number
+        of characters and `?` matches a single character. Defaults to an empty list.
+    */
+    pub ignore_files: Vec<String>,
+    /** Reject spec files with elements or attributes that are not defined by the schema instead of
+        ignoring them (or capturing them as vendor extensions, see `Object::extensions`).
+        This is useful to validate that files exactly match the expected schema, but files with
+        vendor extensions or from newer schema versions are then not loaded at all.
+        Defaults to `false`.
+    */
+    pub deny_unknown_fields: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["xml".to_string()],
+            sort_resources: true,
+            directory_precedence: Precedence::default(),
+            streaming_threshold: None,
+            manifest_fallback: true,
+            ignore_files: Vec::new(),
+            deny_unknown_fields: false,
+        }
+    }
+}
+
+/// Derive the schema version from a schema location (e.g. `LWM2M-v1_1.xsd` results in `1.1`).
+fn schema_version_from_location(location: &str) -> Option<String> {
+    let location = location.split_whitespace().last()?;
+    let file_name = location.rsplit('/').next()?;
+    let stem = file_name
+        .strip_suffix(".xsd")
+        .or_else(|| file_name.strip_suffix(".XSD"))
+        .unwrap_or(file_name);
+    match stem.rsplit_once("-v") {
+        Some((_, version)) => Some(version.replace('_', ".")),
+        None => Some(stem.to_string()),
+    }
+}
+
+/// Error indicating that required objects are not defined (in any version) in the registry.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MissingObjectsError {
+    /// The IDs of the missing objects.
+    pub missing: Vec<u16>,
+}
+
+impl std::fmt::Display for MissingObjectsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ids: Vec<String> = self.missing.iter().map(u16::to_string).collect();
+        write!(f, "Missing required objects: {}", ids.join(", "))
+    }
+}
+
+impl std::error::Error for MissingObjectsError {}
+
+/// Error indicating that objects (ID and version) are defined more than once.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DuplicateObjectsError {
+    /// The object IDs with the versions that are defined more than once.
+    pub duplicates: Vec<(u16, Version)>,
 }
 
+impl std::fmt::Display for DuplicateObjectsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let objects: Vec<String> = self
+            .duplicates
+            .iter()
+            .map(|(id, v)| format!("{} (version {})", id, v))
+            .collect();
+        write!(f, "Duplicate object definitions: {}", objects.join(", "))
+    }
+}
+
+impl std::error::Error for DuplicateObjectsError {}
+
+/// Information about a reload of the registry that is passed to the callbacks registered with `Registry::on_reload`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// The number of objects after the reload.
+    pub object_count: usize,
+    /// The differences between the objects before and after the reload.
+    pub diff: RegistryDiff,
+}
+
+/// The source the objects of a registry are loaded from (again on `reload`).
+enum Source {
+    /// All spec files found in the directories.
+    Directories(Vec<PathBuf>),
+    /// The spec files listed in a manifest.
+    Manifest(PathBuf),
+    /// A SenML-style JSON export of a registry.
+    SenmlJson(PathBuf),
+    /// Spec data that was passed in memory (strings, readers that were consumed or a snapshot).
+    InMemory,
+}
+
+type ReloadCallback = Box<dyn Fn(&ReloadReport) + Send + Sync>;
+
 /** The registry reads spec files from a list of given directories and parses all found specification
     files. The retrieved data can then be queried for various information.
 */
 pub struct Registry {
-    directories: Vec<PathBuf>,
+    source: Source,
+    options: LoadOptions,
+    reusable_resource_ids: HashSet<u16>,
+    aliases: HashMap<String, (u16, Version)>,
+    load_errors: Vec<(PathBuf, String)>,
+    reload_callbacks: Vec<ReloadCallback>,
     /// All the objects that were retrieved from the specification files.
     pub objects: Vec<Object>,
 }
 
+/** Get the object with the highest version. Ties are broken by the source path (lowest wins)
+    and then by the load order (first wins).
+*/
+fn newest<'a>(objects: impl Iterator<Item = &'a Object>) -> Option<&'a Object> {
+    objects.reduce(|best, obj| {
+        let ordering = obj
+            .object_version
+            .cmp(&best.object_version)
+            .then_with(|| best.source_path.cmp(&obj.source_path));
+        if ordering == std::cmp::Ordering::Greater {
+            obj
+        } else {
+            best
+        }
+    })
+}
+
 impl Registry {
     /** Initialize a registry with a number of given directories.
-        The directories are then walked and all XML files that are found are loaded and parsed.
+        The directories are then walked and all spec files that are found are loaded and parsed.
+        These are the XML files, see `LoadOptions::extensions` to load other files (e.g. JSON).
     */
     pub async fn init(directories: Vec<PathBuf>) -> anyhow::Result<Registry> {
-        let dir = directories.clone();
-        let objects = spec_files::load(&dir);
-        let objects = objects.await?;
-        let reg = Registry {
-            directories,
-            objects,
-        };
+        Self::init_with_options(directories, LoadOptions::default()).await
+    }
+
+    /** Initialize a registry with a number of given directories like `init`, but fail with a
+        `DuplicateObjectsError` if an object (ID and version) is defined in more than one spec file.
+    */
+    pub async fn init_strict(directories: Vec<PathBuf>) -> anyhow::Result<Registry> {
+        let reg = Self::init(directories).await?;
+        let duplicates = reg.find_duplicates();
+        if !duplicates.is_empty() {
+            return Err(DuplicateObjectsError { duplicates }.into());
+        }
+        Ok(reg)
+    }
+
+    /** Initialize a registry with a number of given directories and custom load options.
+        The options are kept and used again on `reload`.
+    */
+    pub async fn init_with_options(
+        directories: Vec<PathBuf>,
+        options: LoadOptions,
+    ) -> anyhow::Result<Registry> {
+        let (reg, _) = Self::init_with_report(directories, options).await?;
+        Ok(reg)
+    }
+
+    /** Initialize a registry with a number of given directories and custom load options.
+        Spec files that can't be loaded are skipped. They are returned together with the reason.
+    */
+    pub async fn init_with_report(
+        directories: Vec<PathBuf>,
+        options: LoadOptions,
+    ) -> anyhow::Result<(Registry, Vec<(PathBuf, LoadError)>)> {
+        let (objects, errors) = spec_files::load(&directories, &options).await?;
+        let mut reg = Registry::from_parts(directories, options, objects);
+        reg.record_load_errors(&errors);
+
+        Ok((reg, errors))
+    }
 
+    /** Initialize a registry with a number of given directories. Loading can be aborted with the
+        given cancellation token, in which case a `Cancelled` error is returned.
+    */
+    #[cfg(feature = "cancellation")]
+    pub async fn init_with_cancel(
+        directories: Vec<PathBuf>,
+        token: tokio_util::sync::CancellationToken,
+    ) -> anyhow::Result<Registry> {
+        let options = LoadOptions::default();
+        let (objects, errors) = spec_files::load_cancellable(
+            &directories,
+            &options,
+            &|| token.is_cancelled(),
+            token.cancelled(),
+        )
+        .await?;
+        let mut reg = Registry::from_parts(directories, options, objects);
+        reg.record_load_errors(&errors);
+        Ok(reg)
+    }
+
+    /** Initialize a registry from named sources, e.g. spec files that are read from the network,
+        an archive or a database. Each source is a name and a reader providing the contents of a
+        spec file. The name is used as the source path of the objects and in error messages.
+        Loading fails if a source can't be read or parsed. The objects are kept on `reload`.
+    */
+    pub async fn init_from_sources(
+        sources: impl IntoIterator<Item = (String, Box<dyn AsyncRead + Unpin>)>,
+    ) -> anyhow::Result<Registry> {
+        let options = LoadOptions::default();
+        let mut objects = Vec::new();
+        for (name, reader) in sources {
+            objects.extend(spec_files::load_reader(&name, reader, &options).await?);
+        }
+        let mut reg = Registry::from_parts(Vec::new(), options, objects);
+        reg.source = Source::InMemory;
+        Ok(reg)
+    }
+
+    /** Initialize a registry from the contents of spec files (e.g. bundled with `include_str!`).
+        The specs are parsed exactly like the files loaded by `init`. The objects are kept on `reload`.
+    */
+    pub fn from_specs(specs: Vec<String>) -> anyhow::Result<Registry> {
+        let options = LoadOptions::default();
+        let mut objects = Vec::new();
+        for spec in &specs {
+            objects.extend(spec_files::load_str(spec, None, &options)?);
+        }
+        let mut reg = Registry::from_parts(Vec::new(), options, objects);
+        reg.source = Source::InMemory;
         Ok(reg)
     }
 
-    /// Discard all the current objects and reload all files to populate the list of objects again.
+    /// Create a registry from objects that were loaded from the given directories.
+    pub(crate) fn from_parts(
+        directories: Vec<PathBuf>,
+        options: LoadOptions,
+        objects: Vec<Object>,
+    ) -> Registry {
+        Registry {
+            source: Source::Directories(directories),
+            options,
+            reusable_resource_ids: REUSABLE_RESOURCE_IDS.iter().copied().collect(),
+            aliases: HashMap::new(),
+            load_errors: Vec::new(),
+            reload_callbacks: Vec::new(),
+            objects,
+        }
+    }
+
+    /** Discard all the current objects and reload all files to populate the list of objects again.
+        After a successful reload all callbacks registered with `on_reload` are called.
+    */
     pub async fn reload(&mut self) -> anyhow::Result<()> {
-        self.objects = spec_files::load(&self.directories).await?;
+        let (objects, errors) = match &self.source {
+            Source::Directories(directories) => {
+                spec_files::load(directories, &self.options).await?
+            }
+            Source::Manifest(manifest) => manifest::load(manifest, &self.options).await?,
+            // a SenML export is a single file that either loads completely or fails
+            Source::SenmlJson(path) => (senml::load(path, &self.options).await?, vec![]),
+            // there's nothing to load again, so the objects are kept
+            Source::InMemory => return Ok(()),
+        };
+        self.record_load_errors(&errors);
+        self.replace_objects(objects);
         Ok(())
     }
 
+    /** Get the spec files that were skipped on the last `init` or `reload` because they couldn't
+        be loaded, together with the reason.
+    */
+    pub fn load_errors(&self) -> &[(PathBuf, String)] {
+        &self.load_errors
+    }
+
+    /// Keep the errors of the spec files that couldn't be loaded (see `load_errors`).
+    fn record_load_errors(&mut self, errors: &[(PathBuf, LoadError)]) {
+        self.load_errors = errors
+            .iter()
+            .map(|(path, e)| (path.clone(), e.to_string()))
+            .collect();
+    }
+
+    /// Replace the objects with reloaded ones and call the callbacks registered with `on_reload`.
+    fn replace_objects(&mut self, objects: Vec<Object>) {
+        let old_objects = std::mem::replace(&mut self.objects, objects);
+
+        if !self.reload_callbacks.is_empty() {
+            let report = ReloadReport {
+                object_count: self.objects.len(),
+                diff: diff::diff_objects(&old_objects, &self.objects),
+            };
+            for callback in &self.reload_callbacks {
+                callback(&report);
+            }
+        }
+    }
+
+    /// Register a callback that is called after each successful `reload`. Multiple callbacks can be registered.
+    pub fn on_reload(&mut self, callback: impl Fn(&ReloadReport) + Send + Sync + 'static) {
+        self.reload_callbacks.push(Box::new(callback));
+    }
+
     /// Check if a given object ID with version exists.
     pub fn has_object_id(&self, object_id: u16, version: Version) -> bool {
         self.objects
@@ -295,27 +1112,66 @@ impl Registry {
         None
     }
 
-    /** Get an object ID and it's version for a given name. Returns the object with the highest version. */
+    /** Get an object ID and it's version for a given name. Returns the object with the highest version.
+        If no object has the given name, the name is resolved as alias (see `add_alias`).
+    */
     pub fn get_object_id_by_name_newest(&self, name: &str) -> Option<(u16, Version)> {
-        let mut objs = self
-            .objects
+        newest(self.objects.iter().filter(|o| o.name == name))
+            .map(|obj| (obj.object_id, obj.object_version))
+            .or_else(|| {
+                self.resolve_alias(name)
+                    .map(|o| (o.object_id, o.object_version))
+            })
+    }
+
+    /** Get the object for a given URN (e.g. `urn:oma:lwm2m:oma:3:1.1`). The URN is compared with
+        the URN of the objects (see `Object::urn`). If no object has the given URN, it's resolved
+        as alias (see `add_alias`).
+    */
+    pub fn get_object_by_urn(&self, urn: &str) -> Option<&Object> {
+        self.objects
             .iter()
-            .filter(|o| o.name == name)
-            .collect::<Vec<&Object>>();
-        objs.sort_by_key(|o| &o.object_version);
-        if !objs.is_empty() {
-            if let Some(obj) = objs.pop() {
-                return Some((obj.object_id, obj.object_version));
-            }
-        }
-        None
+            .find(|o| o.urn() == urn)
+            .or_else(|| self.resolve_alias(urn))
+    }
+
+    /** Declare an alias (e.g. the URN or name the object has in another registry) for an object
+        given by object ID with version. The alias is resolved by `get_object_by_urn` and
+        `get_object_id_by_name_newest`. The objects of the registry always take precedence: an alias
+        is only resolved if no object has the alias as URN (or name respectively).
+        Declaring an alias again replaces the previous declaration. Aliases are kept on `reload`.
+    */
+    pub fn add_alias(&mut self, alias_urn: &str, canonical: (u16, Version)) {
+        self.aliases.insert(alias_urn.to_string(), canonical);
     }
 
-    /// Get the object URN for a given object ID with version
+    /// Get the object an alias refers to. Returns `None` if it's not an alias or the object doesn't exist.
+    fn resolve_alias(&self, alias: &str) -> Option<&Object> {
+        let (object_id, version) = self.aliases.get(alias)?;
+        self.get_object_by_id(*object_id, *version)
+    }
+
+    /** Get the object with the highest version for a given object ID.
+        If several objects have the same ID and version the one loaded from the spec file with the
+        lowest path is returned (so the result doesn't depend on the order the files were found).
+    */
+    pub fn get_object_by_id_newest(&self, object_id: u16) -> Option<&Object> {
+        newest(self.objects.iter().filter(|o| o.object_id == object_id))
+    }
+
+    /// Get a resource by ID of the object with the highest version (see `get_object_by_id_newest`).
+    pub fn get_resource_by_id_newest(&self, object_id: u16, resource_id: u16) -> Option<&Resource> {
+        self.get_object_by_id_newest(object_id)?
+            .resources
+            .iter()
+            .find(|r| r.id == resource_id)
+    }
+
+    /// Get the object URN for a given object ID with version. The URN is derived if the spec file doesn't state it.
     pub fn get_object_urn(&self, object_id: u16, version: Version) -> Option<String> {
         let obj = self.get_object_by_id(object_id, version);
         if let Some(obj) = obj {
-            return Some(obj.object_urn.clone());
+            return Some(obj.urn());
         }
         None
     }
@@ -334,6 +1190,28 @@ impl Registry {
         None
     }
 
+    /// Get the units of a resource. Returns `None` if the resource is unknown or has no units.
+    pub fn get_resource_units(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+    ) -> Option<&str> {
+        let res = self.get_resource_by_id(object_id, version, resource_id)?;
+        res.units.as_deref()
+    }
+
+    /// Get the notes (`<Description2>`) of a resource. Returns `None` if the resource is unknown or has no notes.
+    pub fn get_resource_notes(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+    ) -> Option<&str> {
+        let res = self.get_resource_by_id(object_id, version, resource_id)?;
+        res.notes.as_deref()
+    }
+
     /// Get a resource ID by name for a given object ID with version.
     pub fn get_resource_id_by_name(
         &self,
@@ -381,11 +1259,555 @@ impl Registry {
         None
     }
 
-    /// Get all object ID's with their versions.
+    /// Check if the operations of a resource permit a read. Returns `None` if the resource is unknown.
+    pub fn is_resource_readable(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+    ) -> Option<bool> {
+        let res = self.get_resource_by_id(object_id, version, resource_id)?;
+        Some(res.operations.is_read())
+    }
+
+    /// Check if the operations of a resource permit a write. Returns `None` if the resource is unknown.
+    pub fn is_resource_writable(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+    ) -> Option<bool> {
+        let res = self.get_resource_by_id(object_id, version, resource_id)?;
+        Some(res.operations.is_write())
+    }
+
+    /** Get all object ID's with their versions in load order.
+        If an object is defined more than once (see `find_duplicates`) it's also listed more than
+        once. Use `get_unique_object_ids` to get every object only once.
+    */
     pub fn get_object_ids(&self) -> Vec<(u16, Version)> {
         self.objects
             .iter()
             .map(|o| (o.object_id, o.object_version))
             .collect()
     }
+
+    /** Get all object ID's with their versions that define a resource with the given ID.
+        The result is sorted by object ID and then by version.
+    */
+    pub fn objects_defining_resource(&self, resource_id: u16) -> Vec<(u16, Version)> {
+        let mut ids: Vec<(u16, Version)> = self
+            .objects
+            .iter()
+            .filter(|o| o.resources.iter().any(|r| r.id == resource_id))
+            .map(|o| (o.object_id, o.object_version))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /** Check if a value in plain text representation is valid for a resource.
+        The resource is retrieved by object ID with version and resource ID.
+    */
+    pub fn validate_value(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+        value: &str,
+    ) -> Option<bool> {
+        let res = self.get_resource_by_id(object_id, version, resource_id);
+        res.map(|r| r.validate_value(value))
+    }
+
+    /// Group all objects by the LwM2M version they were introduced in.
+    pub fn objects_by_lwm2m_version(&self) -> BTreeMap<Version, Vec<&Object>> {
+        let mut groups: BTreeMap<Version, Vec<&Object>> = BTreeMap::new();
+        for obj in &self.objects {
+            groups.entry(obj.lwm2m_version).or_default().push(obj);
+        }
+        groups
+    }
+
+    /** Get the LwM2M version that is targeted by the most objects.
+        On a tie the higher version is returned.
+    */
+    pub fn most_common_lwm2m_version(&self) -> Option<Version> {
+        self.objects_by_lwm2m_version()
+            .into_iter()
+            .max_by_key(|(_, objs)| objs.len())
+            .map(|(version, _)| version)
+    }
+
+    /// Get the sorted names of all resources of all objects. Each name is listed only once.
+    pub fn all_resource_names(&self) -> Vec<String> {
+        self.objects
+            .iter()
+            .flat_map(|o| o.resources.iter().map(|r| r.name.clone()))
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    }
+
+    /** Get all objects that contain at least one resource of the given type.
+        The result is sorted by object ID and then by version.
+    */
+    pub fn objects_with_resource_type(&self, resource_type: ResourceType) -> Vec<&Object> {
+        let mut objs: Vec<&Object> = self
+            .objects
+            .iter()
+            .filter(|o| o.resources.iter().any(|r| r.resource_type == resource_type))
+            .collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+        objs
+    }
+
+    /** Get the mandatory resources of an optional object for a given object ID with version.
+        These resources are only conditionally mandatory: they are required if the object is
+        implemented at all. For a mandatory object the list is empty because its mandatory
+        resources are required unconditionally.
+    */
+    pub fn conditionally_mandatory_resources(
+        &self,
+        object_id: u16,
+        version: Version,
+    ) -> Option<Vec<&Resource>> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        if obj.is_mandatory {
+            return Some(Vec::new());
+        }
+        Some(obj.resources.iter().filter(|r| r.is_mandatory).collect())
+    }
+
+    /// Get the versions of the XML schemas that the spec files of all objects were authored against.
+    pub fn schema_versions(&self) -> HashSet<String> {
+        self.objects
+            .iter()
+            .filter_map(|o| o.schema_version.clone())
+            .collect()
+    }
+
+    /** Build a map of all resources keyed by their path (e.g. `/3/0`).
+        For every object ID the object version is chosen by the given selector.
+        The map is meant to be built once and then used for fast lookups by path.
+    */
+    pub fn resource_info_map(&self, selector: VersionSelector) -> HashMap<String, ResourceInfo> {
+        let mut selected: BTreeMap<u16, &Object> = BTreeMap::new();
+        for obj in &self.objects {
+            match selector {
+                VersionSelector::Exact(version) if obj.object_version != version => continue,
+                _ => {}
+            }
+            let current = selected.entry(obj.object_id).or_insert(obj);
+            if obj.object_version > current.object_version {
+                *current = obj;
+            }
+        }
+
+        selected
+            .values()
+            .flat_map(|obj| {
+                obj.resources.iter().map(|r| {
+                    (
+                        format!("/{}/{}", obj.object_id, r.id),
+                        ResourceInfo {
+                            object_id: obj.object_id,
+                            object_version: obj.object_version,
+                            resource_id: r.id,
+                            name: r.name.clone(),
+                            operations: r.operations,
+                            has_multiple_instances: r.has_multiple_instances,
+                            is_mandatory: r.is_mandatory,
+                            resource_type: r.resource_type,
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /** Get the object with the highest version that is lower than or equal to the target version.
+        This is useful if there is no spec for the exact version of an object.
+    */
+    pub fn get_object_closest_version(&self, object_id: u16, target: Version) -> Option<&Object> {
+        self.objects
+            .iter()
+            .filter(|o| o.object_id == object_id && o.object_version <= target)
+            .max_by_key(|o| o.object_version)
+    }
+
+    /** Get a resource by ID from the object with the highest version that is lower than or equal
+        to the target version. The version of the object that was actually used is returned as well.
+    */
+    pub fn get_resource_closest_version(
+        &self,
+        object_id: u16,
+        resource_id: u16,
+        target: Version,
+    ) -> Option<(Version, &Resource)> {
+        let obj = self.get_object_closest_version(object_id, target)?;
+        let res = obj.resources.iter().find(|r| r.id == resource_id)?;
+        Some((obj.object_version, res))
+    }
+
+    /// Count the resources per resource type over all objects.
+    pub fn count_by_resource_type(&self) -> HashMap<ResourceType, usize> {
+        let mut counts = HashMap::new();
+        for res in self.objects.iter().flat_map(|o| &o.resources) {
+            *counts.entry(res.resource_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Count the resources per resource type within an object given by object ID with version.
+    pub fn resource_type_distribution(
+        &self,
+        object_id: u16,
+        version: Version,
+    ) -> Option<HashMap<ResourceType, usize>> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        let mut counts = HashMap::new();
+        for res in &obj.resources {
+            *counts.entry(res.resource_type).or_insert(0) += 1;
+        }
+        Some(counts)
+    }
+
+    /// Get all objects that were loaded from spec files within the given directory.
+    pub fn objects_from_directory(&self, directory: &Path) -> Vec<&Object> {
+        self.objects
+            .iter()
+            .filter(|o| {
+                o.source_path
+                    .as_ref()
+                    .is_some_and(|p| p.starts_with(directory))
+            })
+            .collect()
+    }
+
+    /** Get all objects sorted alphabetically by name (case-insensitive).
+        Objects with the same name are sorted by version.
+    */
+    pub fn objects_sorted_by_name(&self) -> Vec<&Object> {
+        let mut objs: Vec<&Object> = self.objects.iter().collect();
+        objs.sort_by_cached_key(|o| (o.name.to_lowercase(), o.object_version));
+        objs
+    }
+
+    /** Check that all the given objects are defined in the registry (in any version).
+        The error lists all required object IDs that are missing.
+    */
+    pub fn require_objects(&self, ids: &[u16]) -> Result<(), MissingObjectsError> {
+        let missing: Vec<u16> = ids
+            .iter()
+            .copied()
+            .filter(|id| !self.objects.iter().any(|o| o.object_id == *id))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingObjectsError { missing })
+        }
+    }
+
+    /** Get all objects whose LwM2M version is strictly less than the given LwM2M version.
+        The result is sorted by object ID and then by version.
+    */
+    pub fn objects_introduced_before(&self, lwm2m_version: Version) -> Vec<&Object> {
+        let mut objs: Vec<&Object> = self
+            .objects
+            .iter()
+            .filter(|o| o.lwm2m_version < lwm2m_version)
+            .collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+        objs
+    }
+
+    /// Get the highest version of an object that is loaded. Returns `None` if the object ID is unknown.
+    pub fn max_object_version(&self, object_id: u16) -> Option<Version> {
+        self.objects
+            .iter()
+            .filter(|o| o.object_id == object_id)
+            .map(|o| o.object_version)
+            .max()
+    }
+
+    /// Get a vendor extension (see `Object::extensions`) of an object given by object ID with version.
+    pub fn get_object_extension(
+        &self,
+        object_id: u16,
+        version: Version,
+        key: &str,
+    ) -> Option<&str> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        obj.extensions.get(key).map(String::as_str)
+    }
+
+    /** Get the IDs of the objects that are defined for both given LwM2M versions (e.g. the stable
+        set of objects across a protocol upgrade). The result is sorted.
+    */
+    pub fn common_object_ids(&self, a: Version, b: Version) -> Vec<u16> {
+        let groups = self.objects_by_lwm2m_version();
+        let ids = |version: Version| -> BTreeSet<u16> {
+            groups
+                .get(&version)
+                .map(|objs| objs.iter().map(|o| o.object_id).collect())
+                .unwrap_or_default()
+        };
+        ids(a).intersection(&ids(b)).copied().collect()
+    }
+
+    /// Get the expected layout of the resources of an instance of an object given by object ID with version.
+    pub fn instance_layout(&self, object_id: u16, version: Version) -> Option<InstanceLayout> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        let mut layout = InstanceLayout::default();
+        for res in &obj.resources {
+            if res.is_mandatory {
+                layout.mandatory.push(res.id);
+            } else {
+                layout.optional.push(res.id);
+            }
+            if res.has_multiple_instances {
+                layout.multi_instance.push(res.id);
+            }
+        }
+        layout.mandatory.sort();
+        layout.optional.sort();
+        layout.multi_instance.sort();
+        Some(layout)
+    }
+
+    /** Get all resources of an object given by object ID with version.
+        An object without resources results in an empty slice, an unknown object in `None`.
+    */
+    pub fn get_resources(&self, object_id: u16, version: Version) -> Option<&[Resource]> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        Some(&obj.resources)
+    }
+
+    /** Get the mandatory resources of an object given by object ID with version.
+        An object without mandatory resources results in an empty list, an unknown object in `None`.
+    */
+    pub fn get_mandatory_resources(
+        &self,
+        object_id: u16,
+        version: Version,
+    ) -> Option<Vec<&Resource>> {
+        let resources = self.get_resources(object_id, version)?;
+        Some(resources.iter().filter(|r| r.is_mandatory).collect())
+    }
+
+    /** Get the resources of an object given by object ID with version that allow exactly the given
+        operations (e.g. `Operations::Read` doesn't match resources with `Operations::ReadWrite`).
+        An object without matching resources results in an empty list, an unknown object in `None`.
+    */
+    pub fn get_resources_by_operation(
+        &self,
+        object_id: u16,
+        version: Version,
+        operations: Operations,
+    ) -> Option<Vec<&Resource>> {
+        let resources = self.get_resources(object_id, version)?;
+        Some(
+            resources
+                .iter()
+                .filter(|r| r.operations == operations)
+                .collect(),
+        )
+    }
+
+    /// Get the number of resources of an object given by object ID with version.
+    pub fn resource_count(&self, object_id: u16, version: Version) -> Option<usize> {
+        self.get_resources(object_id, version)
+            .map(<[Resource]>::len)
+    }
+
+    /// Get the highest resource ID of an object. Returns `None` if the object is unknown or has no resources.
+    pub fn max_resource_id(&self, object_id: u16, version: Version) -> Option<u16> {
+        self.get_resources(object_id, version)?
+            .iter()
+            .map(|r| r.id)
+            .max()
+    }
+
+    /** Find all objects whose URN ends with the given suffix (e.g. `3:1.1`).
+        The suffix has to match whole URN segments, so `3:1.1` matches `urn:oma:lwm2m:oma:3:1.1`
+        but not `urn:oma:lwm2m:oma:13:1.1`. Derived URNs are used for objects without an URN.
+        The result is sorted by object ID and then by version.
+    */
+    pub fn find_objects_by_urn_suffix(&self, suffix: &str) -> Vec<&Object> {
+        let mut objs: Vec<&Object> = self
+            .objects
+            .iter()
+            .filter(|o| {
+                let urn = o.urn();
+                urn.strip_suffix(suffix).is_some_and(|rest| {
+                    rest.is_empty() || rest.ends_with(':') || suffix.starts_with(':')
+                })
+            })
+            .collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+        objs
+    }
+
+    /// Get a resource together with the name of the object it belongs to (e.g. for log messages).
+    pub fn resource_with_object_name(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+    ) -> Option<(String, &Resource)> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        let res = obj.resources.iter().find(|r| r.id == resource_id)?;
+        Some((obj.name.clone(), res))
+    }
+
+    /// Get the number of loaded versions of an object (0 if the object ID is unknown).
+    pub fn version_count(&self, object_id: u16) -> usize {
+        self.objects
+            .iter()
+            .filter(|o| o.object_id == object_id)
+            .map(|o| o.object_version)
+            .collect::<BTreeSet<Version>>()
+            .len()
+    }
+
+    /** Check if an object supports the given LwM2M version. The target has to be at least the
+        LwM2M version of the object and at most its `lwm2m_version_max` (if declared).
+        Returns `None` if the object is unknown.
+    */
+    pub fn object_supports_lwm2m(
+        &self,
+        object_id: u16,
+        version: Version,
+        target: Version,
+    ) -> Option<bool> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        let below_max = match obj.lwm2m_version_max {
+            Some(max) => target <= max,
+            None => true,
+        };
+        Some(obj.lwm2m_version <= target && below_max)
+    }
+
+    /// Count the resources per operations within an object given by object ID with version.
+    pub fn resource_count_by_operation(
+        &self,
+        object_id: u16,
+        version: Version,
+    ) -> Option<HashMap<Operations, usize>> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        let mut counts = HashMap::new();
+        for res in &obj.resources {
+            *counts.entry(res.operations).or_insert(0) += 1;
+        }
+        Some(counts)
+    }
+
+    /** Get all objects that define at least one executable resource. The result is sorted by object ID
+        and version.
+    */
+    pub fn objects_with_executable_resources(&self) -> Vec<&Object> {
+        let mut objs: Vec<&Object> = self
+            .objects
+            .iter()
+            .filter(|o| o.resources.iter().any(|r| r.operations.is_execute()))
+            .collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+        objs
+    }
+
+    /** Parse the contents of a spec file and add its objects to the registry.
+        Note: The added objects are discarded on `reload` unless the registry was created in memory
+        (e.g. with `from_specs`).
+    */
+    pub fn add_spec_str(&mut self, xml: &str) -> anyhow::Result<()> {
+        let objects = spec_files::load_str(xml, None, &self.options)?;
+        self.objects.extend(objects);
+        Ok(())
+    }
+
+    /** Get the IDs of the resources of an object that are within the given (inclusive) range.
+        The IDs are sorted. Returns `None` if the object doesn't exist.
+    */
+    pub fn resource_ids_in_range(
+        &self,
+        object_id: u16,
+        version: Version,
+        start: u16,
+        end: u16,
+    ) -> Option<Vec<u16>> {
+        let obj = self.get_object_by_id(object_id, version)?;
+        let mut ids: Vec<u16> = obj
+            .resources
+            .iter()
+            .map(|r| r.id)
+            .filter(|id| (start..=end).contains(id))
+            .collect();
+        ids.sort();
+        Some(ids)
+    }
+
+    /** Get all object ID's with their versions that are defined more than once (e.g. in a draft
+        and a final copy of a spec file). The result is sorted by object ID and then by version.
+    */
+    pub fn find_duplicates(&self) -> Vec<(u16, Version)> {
+        let mut counts: BTreeMap<(u16, Version), usize> = BTreeMap::new();
+        for obj in &self.objects {
+            *counts
+                .entry((obj.object_id, obj.object_version))
+                .or_insert(0) += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Get all object ID's with their versions, every object only once. The result is sorted by object ID and then by version.
+    pub fn get_unique_object_ids(&self) -> Vec<(u16, Version)> {
+        let ids: BTreeSet<(u16, Version)> = self
+            .objects
+            .iter()
+            .map(|o| (o.object_id, o.object_version))
+            .collect();
+        ids.into_iter().collect()
+    }
+
+    /** Get all objects that were introduced in or before the given LwM2M version (i.e. whose
+        LwM2M version is less than or equal to it). The result is sorted by object ID and then by version.
+    */
+    pub fn get_objects_for_lwm2m_version(&self, version: Version) -> Vec<&Object> {
+        let mut objs: Vec<&Object> = self
+            .objects
+            .iter()
+            .filter(|o| o.lwm2m_version <= version)
+            .collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+        objs
+    }
+
+    /// Get the object ID's with their versions of all objects returned by `get_objects_for_lwm2m_version`.
+    pub fn get_object_ids_for_lwm2m_version(&self, version: Version) -> Vec<(u16, Version)> {
+        self.get_objects_for_lwm2m_version(version)
+            .into_iter()
+            .map(|o| (o.object_id, o.object_version))
+            .collect()
+    }
+
+    /** Resolve the object an object link resource (given by object ID with version and resource ID)
+        refers to (see `Resource::object_link_target`). The newest loaded version of the target
+        object is returned. Returns `None` if the resource isn't an object link, its target isn't
+        documented or the target object isn't loaded.
+    */
+    pub fn resolve_object_link(
+        &self,
+        object_id: u16,
+        version: Version,
+        resource_id: u16,
+    ) -> Option<&Object> {
+        let res = self.get_resource_by_id(object_id, version, resource_id)?;
+        self.get_object_by_id_newest(res.object_link_target()?)
+    }
 }
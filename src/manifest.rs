@@ -0,0 +1,108 @@
+use crate::spec_files::FileErrors;
+use crate::{spec_files, LoadError, LoadOptions, Object, Registry, Source, Version};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/** A manifest (e.g. `index.json`) that lists the spec files to load:
+    ```json
+    {
+      "files": [
+        { "path": "lwm2m_1_1/3-1_1.xml", "object_id": 3, "version": "1.1" }
+      ]
+    }
+    ```
+    The paths are relative to the directory of the manifest. The object ID and version are
+    optional. If given, the file needs to define that object.
+*/
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    path: PathBuf,
+    object_id: Option<u16>,
+    version: Option<String>,
+}
+
+/** Load the spec files listed in a manifest. If the manifest doesn't exist, its directory may be
+    walked instead. In that case the files that can't be loaded are skipped and reported together
+    with the reason. A listed file that can't be loaded is an error.
+*/
+pub(crate) async fn load(
+    manifest: &Path,
+    options: &LoadOptions,
+) -> anyhow::Result<(Vec<Object>, FileErrors)> {
+    let base_dir = manifest.parent().unwrap_or(Path::new("")).to_path_buf();
+    if !tokio::fs::try_exists(manifest).await? && options.manifest_fallback {
+        return spec_files::load(&[base_dir], options).await;
+    }
+
+    let contents = tokio::fs::read_to_string(manifest).await?;
+    let manifest: Manifest = serde_json::from_str(&contents)?;
+
+    let mut objects = Vec::new();
+    for entry in manifest.files {
+        let path = base_dir.join(&entry.path);
+        let file_objects = spec_files::load_spec_file(&path, options).await?;
+
+        if let Some(object_id) = entry.object_id {
+            let version = entry.version.as_deref().map(Version::from_str).transpose();
+            let version = version.map_err(|e| anyhow::anyhow!("{:?}", e))?;
+            let listed = file_objects.iter().any(|o| {
+                o.object_id == object_id
+                    && match version {
+                        Some(v) => v == o.object_version,
+                        None => true,
+                    }
+            });
+            if !listed {
+                anyhow::bail!(
+                    "{} doesn't define object {} as listed in the manifest",
+                    path.display(),
+                    object_id
+                );
+            }
+        }
+        objects.extend(file_objects);
+    }
+    Ok((objects, Vec::new()))
+}
+
+impl Registry {
+    /** Initialize a registry from a manifest file (e.g. `index.json`) that lists the spec files to
+        load, instead of walking whole directories. If the manifest doesn't exist, the directory
+        of the manifest is walked instead.
+    */
+    pub async fn init_from_manifest(manifest: PathBuf) -> anyhow::Result<Registry> {
+        Self::init_from_manifest_with_options(manifest, LoadOptions::default()).await
+    }
+
+    /** Initialize a registry from a manifest file with custom load options.
+        See `LoadOptions::manifest_fallback` to disable walking the directory if the manifest is missing.
+    */
+    pub async fn init_from_manifest_with_options(
+        manifest: PathBuf,
+        options: LoadOptions,
+    ) -> anyhow::Result<Registry> {
+        let (reg, _) = Self::init_from_manifest_with_report(manifest, options).await?;
+        Ok(reg)
+    }
+
+    /** Initialize a registry from a manifest file with custom load options. If the directory of a
+        missing manifest is walked instead, the spec files that can't be loaded are skipped. They are
+        returned together with the reason.
+    */
+    pub async fn init_from_manifest_with_report(
+        manifest: PathBuf,
+        options: LoadOptions,
+    ) -> anyhow::Result<(Registry, Vec<(PathBuf, LoadError)>)> {
+        let (objects, errors) = load(&manifest, &options).await?;
+        let mut reg = Registry::from_parts(Vec::new(), options, objects);
+        reg.source = Source::Manifest(manifest);
+        reg.record_load_errors(&errors);
+        Ok((reg, errors))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::serialize::{operations_token, type_token};
+use crate::{Object, Registry};
+use std::fmt::Write;
+
+/// Escape a value for a cell of a Markdown table.
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "Yes"
+    } else {
+        "No"
+    }
+}
+
+/// Write the section of an object with a table of its resources.
+fn write_object(out: &mut String, obj: &Object) -> std::fmt::Result {
+    writeln!(
+        out,
+        "## {} (ID {}, version {})\n",
+        cell(&obj.name),
+        obj.object_id,
+        obj.object_version
+    )?;
+    writeln!(
+        out,
+        "LwM2M version: {}, mandatory: {}, multiple instances: {}\n",
+        obj.lwm2m_version,
+        yes_no(obj.is_mandatory),
+        yes_no(obj.has_multiple_instances)
+    )?;
+    writeln!(
+        out,
+        "| ID | Name | Operations | Type | Mandatory | Multiple Instances | Units | Description |"
+    )?;
+    writeln!(out, "|---|---|---|---|---|---|---|---|")?;
+    for res in &obj.resources {
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} | {} | {} |",
+            res.id,
+            cell(&res.name),
+            operations_token(res.operations),
+            type_token(res.resource_type),
+            yes_no(res.is_mandatory),
+            yes_no(res.has_multiple_instances),
+            cell(res.units.as_deref().unwrap_or_default()),
+            cell(res.description.as_deref().unwrap_or_default())
+        )?;
+    }
+    Ok(())
+}
+
+impl Registry {
+    /** Create a Markdown documentation of all objects. Each object (with version) gets its own
+        section with a table of its resources. The sections are sorted by object ID and version.
+    */
+    pub fn to_markdown(&self) -> String {
+        let mut objs: Vec<&Object> = self.objects.iter().collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+
+        let mut out = String::new();
+        for (i, obj) in objs.into_iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            write_object(&mut out, obj).expect("Writing to a String can't fail");
+        }
+        out
+    }
+}
@@ -0,0 +1,174 @@
+//! Helpers to build and validate LwM2M paths like `/3/0/9`.
+use crate::{Registry, Version};
+use std::fmt::{Display, Formatter};
+
+/// Error indicating that a path is not valid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// The object (with the given version) is not known.
+    UnknownObject(u16),
+    /// The resource is not defined by the object.
+    UnknownResource(u16),
+    /// The instance ID is not allowed for a single-instance object.
+    InvalidInstance(u16),
+    /// A resource was given without an instance.
+    MissingInstance,
+    /// The path is not of the form `/object[/instance[/resource]]`.
+    Malformed,
+    /// A path segment is not a number or is out of range.
+    InvalidId,
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::UnknownObject(id) => write!(f, "Unknown object: {}", id),
+            PathError::UnknownResource(id) => write!(f, "Unknown resource: {}", id),
+            PathError::InvalidInstance(id) => {
+                write!(f, "Invalid instance for single-instance object: {}", id)
+            }
+            PathError::MissingInstance => write!(f, "A resource requires an instance"),
+            PathError::Malformed => write!(f, "Malformed path"),
+            PathError::InvalidId => write!(f, "Invalid ID in path"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/** Format a canonical LwM2M path like `/3/0/9`, `/3/0` or `/3`.
+    The resource ID is only included if an instance ID is given.
+*/
+pub fn format_path(object_id: u16, instance_id: Option<u16>, resource_id: Option<u16>) -> String {
+    match (instance_id, resource_id) {
+        (Some(instance_id), Some(resource_id)) => {
+            format!("/{}/{}/{}", object_id, instance_id, resource_id)
+        }
+        (Some(instance_id), None) => format!("/{}/{}", object_id, instance_id),
+        (None, _) => format!("/{}", object_id),
+    }
+}
+
+/** Parse a LwM2M path like `/3/0/9`, `/3/0` or `/3` into the object ID and the optional instance
+    and resource IDs.
+*/
+pub fn parse_path_ids(path: &str) -> Result<(u16, Option<u16>, Option<u16>), PathError> {
+    let segments = path.strip_prefix('/').ok_or(PathError::Malformed)?;
+    let ids = segments
+        .split('/')
+        .map(|s| match s {
+            "" => Err(PathError::Malformed),
+            s if s.bytes().all(|b| b.is_ascii_digit()) => {
+                s.parse::<u16>().map_err(|_| PathError::InvalidId)
+            }
+            _ => Err(PathError::InvalidId),
+        })
+        .collect::<Result<Vec<u16>, PathError>>()?;
+
+    match ids[..] {
+        [object_id] => Ok((object_id, None, None)),
+        [object_id, instance_id] => Ok((object_id, Some(instance_id), None)),
+        [object_id, instance_id, resource_id] => {
+            Ok((object_id, Some(instance_id), Some(resource_id)))
+        }
+        _ => Err(PathError::Malformed),
+    }
+}
+
+impl Registry {
+    /** Format a canonical LwM2M path and validate it against the registry.
+        The object (with the given version) and the resource need to exist, an instance other than
+        `0` is rejected for single-instance objects and a resource requires an instance.
+    */
+    pub fn format_valid_path(
+        &self,
+        object_id: u16,
+        version: Version,
+        instance_id: Option<u16>,
+        resource_id: Option<u16>,
+    ) -> Result<String, PathError> {
+        let obj = self
+            .get_object_by_id(object_id, version)
+            .ok_or(PathError::UnknownObject(object_id))?;
+
+        if let Some(instance_id) = instance_id {
+            if instance_id > 0 && !obj.has_multiple_instances {
+                return Err(PathError::InvalidInstance(instance_id));
+            }
+        }
+
+        if let Some(resource_id) = resource_id {
+            if instance_id.is_none() {
+                return Err(PathError::MissingInstance);
+            }
+            if !obj.resources.iter().any(|r| r.id == resource_id) {
+                return Err(PathError::UnknownResource(resource_id));
+            }
+        }
+
+        Ok(format_path(object_id, instance_id, resource_id))
+    }
+
+    /** Describe a LwM2M path for diagnostics, e.g.
+        `Object 3 (Device) v1.1 → Resource 0 (Manufacturer, String, Read)` for `/3/0/0`.
+        Paths with only an object (`/3`) or an object and instance (`/3/0`) are described
+        accordingly. Invalid paths and unknown objects or resources are described as such.
+    */
+    pub fn explain_path(&self, path: &str, version: Version) -> String {
+        let (object_id, instance_id, resource_id) = match parse_path_ids(path) {
+            Ok(ids) => ids,
+            Err(e) => return format!("Invalid path {}: {}", path, e),
+        };
+        let Some(obj) = self.get_object_by_id(object_id, version) else {
+            return format!("Object {} v{} is unknown", object_id, version);
+        };
+        let object = format!("Object {} ({}) v{}", obj.object_id, obj.name, version);
+
+        match (instance_id, resource_id) {
+            (None, _) => object,
+            (Some(instance_id), None) => format!("{} → Instance {}", object, instance_id),
+            (Some(_), Some(resource_id)) => {
+                match obj.resources.iter().find(|r| r.id == resource_id) {
+                    Some(res) => format!(
+                        "{} → Resource {} ({}, {:?}, {:?})",
+                        object, res.id, res.name, res.resource_type, res.operations
+                    ),
+                    None => format!("{} → Resource {} is unknown", object, resource_id),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::{format_path, parse_path_ids, PathError};
+
+    #[test]
+    fn test_format_path() {
+        assert_eq!(format_path(3, None, None), "/3");
+        assert_eq!(format_path(3, Some(0), None), "/3/0");
+        assert_eq!(format_path(3, Some(0), Some(9)), "/3/0/9");
+    }
+
+    #[test]
+    fn test_parse_path_ids() {
+        assert_eq!(parse_path_ids("/3"), Ok((3, None, None)));
+        assert_eq!(parse_path_ids("/3/0"), Ok((3, Some(0), None)));
+        assert_eq!(parse_path_ids("/3/0/9"), Ok((3, Some(0), Some(9))));
+        assert_eq!(parse_path_ids("/65535/1/2"), Ok((65535, Some(1), Some(2))));
+    }
+
+    #[test]
+    fn test_parse_path_ids_malformed() {
+        assert_eq!(parse_path_ids(""), Err(PathError::Malformed));
+        assert_eq!(parse_path_ids("/"), Err(PathError::Malformed));
+        assert_eq!(parse_path_ids("3/0"), Err(PathError::Malformed));
+        assert_eq!(parse_path_ids("/3/"), Err(PathError::Malformed));
+        assert_eq!(parse_path_ids("/3//9"), Err(PathError::Malformed));
+        assert_eq!(parse_path_ids("/3/0/9/1"), Err(PathError::Malformed));
+        assert_eq!(parse_path_ids("/a/0"), Err(PathError::InvalidId));
+        assert_eq!(parse_path_ids("/3/+0"), Err(PathError::InvalidId));
+        assert_eq!(parse_path_ids("/65536"), Err(PathError::InvalidId));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{Object, Registry, Resource, ResourceType, Version};
+
+/** A query over the objects and resources of a registry that is built by chaining predicates.
+    Created with `Registry::query`.
+
+    All predicates are combined (a result has to match all of them). `object_id` and
+    `min_version` restrict the objects, `mandatory` and `resource_type` restrict the resources:
+    `resources` returns the matching resources of the matching objects, `objects` returns the
+    matching objects that have at least one matching resource (if a resource predicate is set).
+    A predicate that is set again replaces the previous value.
+*/
+#[derive(Clone)]
+pub struct RegistryQuery<'a> {
+    registry: &'a Registry,
+    object_id: Option<u16>,
+    min_version: Option<Version>,
+    mandatory: Option<bool>,
+    resource_type: Option<ResourceType>,
+}
+
+impl<'a> RegistryQuery<'a> {
+    fn new(registry: &'a Registry) -> Self {
+        Self {
+            registry,
+            object_id: None,
+            min_version: None,
+            mandatory: None,
+            resource_type: None,
+        }
+    }
+
+    /// Only match the object with the given ID.
+    pub fn object_id(mut self, object_id: u16) -> Self {
+        self.object_id = Some(object_id);
+        self
+    }
+
+    /// Only match objects with at least the given object version.
+    pub fn min_version(mut self, version: Version) -> Self {
+        self.min_version = Some(version);
+        self
+    }
+
+    /// Only match resources that are mandatory (`true`) or optional (`false`).
+    pub fn mandatory(mut self, mandatory: bool) -> Self {
+        self.mandatory = Some(mandatory);
+        self
+    }
+
+    /// Only match resources of the given type.
+    pub fn resource_type(mut self, resource_type: ResourceType) -> Self {
+        self.resource_type = Some(resource_type);
+        self
+    }
+
+    fn has_resource_predicate(&self) -> bool {
+        self.mandatory.is_some() || self.resource_type.is_some()
+    }
+
+    fn matches_object(&self, obj: &Object) -> bool {
+        let min_version = match self.min_version {
+            Some(v) => obj.object_version >= v,
+            None => true,
+        };
+        matches(self.object_id, obj.object_id) && min_version
+    }
+
+    fn matches_resource(&self, res: &Resource) -> bool {
+        matches(self.mandatory, res.is_mandatory) && matches(self.resource_type, res.resource_type)
+    }
+
+    fn matching_objects(&self) -> Vec<&'a Object> {
+        let mut objs: Vec<&Object> = self
+            .registry
+            .objects
+            .iter()
+            .filter(|o| self.matches_object(o))
+            .collect();
+        objs.sort_by_key(|o| (o.object_id, o.object_version));
+        objs
+    }
+
+    /// Get the matching objects, sorted by object ID and version.
+    pub fn objects(&self) -> Vec<&'a Object> {
+        let mut objs = self.matching_objects();
+        if self.has_resource_predicate() {
+            objs.retain(|o| o.resources.iter().any(|r| self.matches_resource(r)));
+        }
+        objs
+    }
+
+    /// Get the matching resources together with their objects, sorted by object ID and version.
+    pub fn resources(&self) -> Vec<(&'a Object, &'a Resource)> {
+        self.matching_objects()
+            .into_iter()
+            .flat_map(|o| o.resources.iter().map(move |r| (o, r)))
+            .filter(|(_, r)| self.matches_resource(r))
+            .collect()
+    }
+}
+
+/// Check if a value matches an optional filter (no filter matches all values).
+fn matches<T: PartialEq>(filter: Option<T>, value: T) -> bool {
+    match filter {
+        Some(f) => f == value,
+        None => true,
+    }
+}
+
+impl Registry {
+    /// Create a query over the objects and resources of the registry. See `RegistryQuery`.
+    pub fn query(&self) -> RegistryQuery<'_> {
+        RegistryQuery::new(self)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Registry, Resource};
+use std::collections::HashSet;
+
+/** IDs of commonly used reusable resources. Unlike object specific resources (IDs below 2048),
+    reusable resources have the same meaning in every object that uses them.
+    The list is a selection from the reusable resources of the OMA LwM2M registry
+    (<https://technical.openmobilealliance.org/OMNA/LwM2M/LwM2MRegistry.html>).
+    Use `Registry::set_reusable_resource_ids` to use a different list.
+*/
+pub const REUSABLE_RESOURCE_IDS: &[u16] = &[
+    5500, // Digital Input State
+    5501, // Digital Input Counter
+    5505, // Digital Input Counter Reset
+    5550, // Digital Output State
+    5600, // Analog Input Current Value
+    5601, // Min Measured Value
+    5602, // Max Measured Value
+    5603, // Min Range Value
+    5604, // Max Range Value
+    5605, // Reset Min and Max Measured Values
+    5650, // Analog Output Current Value
+    5700, // Sensor Value
+    5701, // Sensor Units
+    5750, // Application Type
+    5751, // Sensor Type
+    5800, // Instantaneous active power
+    5805, // Cumulative active power
+    5850, // On/Off
+    5851, // Dimmer
+    5852, // On time
+];
+
+impl Resource {
+    /** Check if the resource is one of the bundled reusable resources (see `REUSABLE_RESOURCE_IDS`).
+        A list that is set with `Registry::set_reusable_resource_ids` is not taken into account,
+        use `Registry::is_reusable_resource` for that.
+    */
+    pub fn is_reusable_common(&self) -> bool {
+        REUSABLE_RESOURCE_IDS.contains(&self.id)
+    }
+}
+
+impl Registry {
+    /** Replace the list of reusable resource IDs that is used by `is_reusable_resource` and
+        `reusable_resources_used`.
+    */
+    pub fn set_reusable_resource_ids(&mut self, ids: HashSet<u16>) {
+        self.reusable_resource_ids = ids;
+    }
+
+    /// Check if a resource ID is in the list of reusable resource IDs of the registry.
+    pub fn is_reusable_resource(&self, id: u16) -> bool {
+        self.reusable_resource_ids.contains(&id)
+    }
+
+    /// Get the IDs of all reusable resources that are used by any of the loaded objects.
+    pub fn reusable_resources_used(&self) -> HashSet<u16> {
+        self.objects
+            .iter()
+            .flat_map(|o| &o.resources)
+            .map(|r| r.id)
+            .filter(|id| self.is_reusable_resource(*id))
+            .collect()
+    }
+}
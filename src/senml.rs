@@ -0,0 +1,137 @@
+use crate::deserialize::parse_operations;
+use crate::{LoadOptions, Object, Registry, Resource, ResourceType, Source, Version};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A record of a SenML-style JSON export. See `Registry::init_from_senml_json` for the format.
+#[derive(Debug, Deserialize)]
+struct Record {
+    bn: Option<String>,
+    n: Option<String>,
+    on: Option<String>,
+    ver: Option<String>,
+    lv: Option<String>,
+    urn: Option<String>,
+    rn: Option<String>,
+    #[serde(default)]
+    op: String,
+    #[serde(rename = "type", default)]
+    resource_type: String,
+    #[serde(default)]
+    mi: bool,
+    #[serde(default)]
+    mand: bool,
+    u: Option<String>,
+    desc: Option<String>,
+}
+
+fn parse_version(version: Option<&str>) -> anyhow::Result<Version> {
+    match version {
+        Some(v) => Version::from_str(v).map_err(|e| anyhow::anyhow!("{:?}", e)),
+        None => Ok(Version::new(1, 0)),
+    }
+}
+
+fn parse_object(record: Record, base_name: &str) -> anyhow::Result<Object> {
+    let object_id = base_name
+        .trim_matches('/')
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid object base name: {}", base_name))?;
+    Ok(Object {
+        name: record.on.unwrap_or_default(),
+        object_id,
+        object_urn: record.urn,
+        object_version: parse_version(record.ver.as_deref())?,
+        lwm2m_version: parse_version(record.lv.as_deref())?,
+        lwm2m_version_max: None,
+        has_multiple_instances: record.mi,
+        is_mandatory: record.mand,
+        resources: Vec::new(),
+        source_path: None,
+        schema_version: None,
+        extensions: HashMap::new(),
+    })
+}
+
+fn parse_resource(record: Record) -> anyhow::Result<Resource> {
+    let name = record.n.unwrap_or_default();
+    let id = name
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid resource name: {}", name))?;
+    let resource_type =
+        ResourceType::from_str(&record.resource_type).map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    let operations = parse_operations(&record.op)
+        .ok_or_else(|| anyhow::anyhow!("Invalid operations: {}", record.op))?;
+    let mut resource = Resource::new(
+        id,
+        record.rn.unwrap_or_default(),
+        operations,
+        record.mi,
+        record.mand,
+        resource_type,
+    );
+    resource.units = record.u;
+    resource.description = record.desc;
+    Ok(resource)
+}
+
+/// Parse the objects of a SenML-style JSON export.
+pub(crate) fn parse(json: &str) -> anyhow::Result<Vec<Object>> {
+    let records: Vec<Record> = serde_json::from_str(json)?;
+
+    let mut objects: Vec<Object> = Vec::new();
+    for record in records {
+        match record.bn.clone() {
+            Some(base_name) => objects.push(parse_object(record, &base_name)?),
+            None => {
+                let object = objects
+                    .last_mut()
+                    .ok_or_else(|| anyhow::anyhow!("Resource record without object record"))?;
+                object.resources.push(parse_resource(record)?);
+            }
+        }
+    }
+    Ok(objects)
+}
+
+/// Load the objects of a SenML-style JSON export.
+pub(crate) async fn load(path: &Path, options: &LoadOptions) -> anyhow::Result<Vec<Object>> {
+    let contents = tokio::fs::read_to_string(path).await?;
+    let mut objects = parse(&contents)?;
+    for object in &mut objects {
+        if options.sort_resources {
+            object.resources.sort_by_key(|r| r.id);
+        }
+        object.source_path = Some(path.to_path_buf());
+    }
+    Ok(objects)
+}
+
+impl Registry {
+    /** Initialize a registry from a SenML-style JSON export of a registry (instead of spec files).
+        The export is a flat array of records:
+        ```json
+        [
+          { "bn": "/3/", "on": "Device", "ver": "1.1", "lv": "1.1", "mi": false, "mand": true },
+          { "n": "0", "rn": "Manufacturer", "op": "R", "type": "String" },
+          { "n": "4", "rn": "Reboot", "op": "E", "mand": true }
+        ]
+        ```
+        A record with a base name (`bn`) of the form `/<object ID>/` starts a new object. It has the
+        object name (`on`), the object version (`ver`), the LwM2M version (`lv`) and optionally the
+        URN (`urn`). The versions default to `1.0`.
+        All following records without base name are resources of that object. Their name (`n`) is
+        the resource ID. They have the resource name (`rn`), the operations (`op`), the type
+        (`type`) and optionally the units (`u`) and the description (`desc`).
+        The flags for multiple instances (`mi`) and mandatory (`mand`) default to `false`.
+    */
+    pub async fn init_from_senml_json(path: PathBuf) -> anyhow::Result<Registry> {
+        let options = LoadOptions::default();
+        let objects = load(&path, &options).await?;
+        let mut reg = Registry::from_parts(Vec::new(), options, objects);
+        reg.source = Source::SenmlJson(path);
+        Ok(reg)
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{Object, Operations, Registry, Resource, ResourceType, Version};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// The location of the XML schema that is referenced by spec files of the given schema version.
+const SCHEMA_LOCATION: &str = "http://www.openmobilealliance.org/tech/profiles/";
+
+/// The token of the operations as used in spec files (e.g. `RW`).
+pub(crate) fn operations_token(operations: Operations) -> &'static str {
+    match operations {
+        Operations::Read => "R",
+        Operations::Write => "W",
+        Operations::ReadWrite => "RW",
+        Operations::Execute => "E",
+        Operations::ReadExecute => "RE",
+        Operations::WriteExecute => "WE",
+        Operations::ReadWriteExecute => "RWE",
+        Operations::None => "",
+    }
+}
+
+/// The token of the resource type as used in spec files (e.g. `Objlnk`).
+pub(crate) fn type_token(resource_type: ResourceType) -> &'static str {
+    match resource_type {
+        ResourceType::String => "String",
+        ResourceType::Integer => "Integer",
+        ResourceType::Float => "Float",
+        ResourceType::Boolean => "Boolean",
+        ResourceType::Opaque => "Opaque",
+        ResourceType::Time => "Time",
+        ResourceType::ObjectLink => "Objlnk",
+        ResourceType::UnsignedInteger => "Unsigned Integer",
+        ResourceType::Corelink => "Corelnk",
+        ResourceType::Other => "",
+    }
+}
+
+fn multiple_instances_token(has_multiple_instances: bool) -> &'static str {
+    if has_multiple_instances {
+        "Multiple"
+    } else {
+        "Single"
+    }
+}
+
+fn mandatory_token(is_mandatory: bool) -> &'static str {
+    if is_mandatory {
+        "Mandatory"
+    } else {
+        "Optional"
+    }
+}
+
+/** The schema location for a schema version (see `LwM2MSpec::schema_version`).
+    E.g. `1.1` results in `http://www.openmobilealliance.org/tech/profiles/LWM2M-v1_1.xsd`.
+*/
+fn schema_location(schema_version: &str) -> String {
+    if Version::parse_with_minor_explicit(schema_version).is_ok() {
+        format!(
+            "{}LWM2M-v{}.xsd",
+            SCHEMA_LOCATION,
+            schema_version.replace('.', "_")
+        )
+    } else {
+        format!("{}{}.xsd", SCHEMA_LOCATION, schema_version)
+    }
+}
+
+/// Write an element that only contains text.
+fn write_text<W: Write>(writer: &mut Writer<W>, name: &str, text: &str) -> std::io::Result<()> {
+    writer
+        .create_element(name)
+        .write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+/** Write the vendor extensions as elements, sorted by name. Namespaced extensions (e.g. `acme:id`,
+    which can only be captured from attributes) are left out because the declarations of their
+    namespaces are not kept.
+*/
+fn write_extensions<W: Write>(
+    writer: &mut Writer<W>,
+    extensions: &HashMap<String, String>,
+) -> std::io::Result<()> {
+    let mut elements: Vec<(&String, &String)> = extensions
+        .iter()
+        .filter(|(key, _)| !key.contains(':'))
+        .collect();
+    elements.sort();
+    for (key, value) in elements {
+        write_text(writer, key, value)?;
+    }
+    Ok(())
+}
+
+fn write_resource<W: Write>(writer: &mut Writer<W>, res: &Resource) -> std::io::Result<()> {
+    let id = res.id.to_string();
+    let start = BytesStart::new("Item").with_attributes([("ID", id.as_str())]);
+    writer.write_event(Event::Start(start))?;
+    write_text(writer, "Name", &res.name)?;
+    write_text(writer, "Operations", operations_token(res.operations))?;
+    write_text(
+        writer,
+        "MultipleInstances",
+        multiple_instances_token(res.has_multiple_instances),
+    )?;
+    write_text(writer, "Mandatory", mandatory_token(res.is_mandatory))?;
+    write_text(writer, "Type", type_token(res.resource_type))?;
+    write_text(
+        writer,
+        "RangeEnumeration",
+        res.range_enumeration.as_deref().unwrap_or_default(),
+    )?;
+    write_text(writer, "Units", res.units.as_deref().unwrap_or_default())?;
+    write_text(
+        writer,
+        "Description",
+        res.description.as_deref().unwrap_or_default(),
+    )?;
+    if let Some(notes) = &res.notes {
+        write_text(writer, "Description2", notes)?;
+    }
+    write_extensions(writer, &res.extensions)?;
+    writer.write_event(Event::End(BytesEnd::new("Item")))
+}
+
+fn write_object<W: Write>(writer: &mut Writer<W>, obj: &Object) -> std::io::Result<()> {
+    let start = BytesStart::new("Object").with_attributes([("ObjectType", "MODefinition")]);
+    writer.write_event(Event::Start(start))?;
+    write_text(writer, "Name", &obj.name)?;
+    write_text(writer, "Description1", "")?;
+    write_text(writer, "ObjectID", &obj.object_id.to_string())?;
+    write_text(
+        writer,
+        "ObjectURN",
+        obj.object_urn.as_deref().unwrap_or_default(),
+    )?;
+    write_text(writer, "LWM2MVersion", &obj.lwm2m_version.to_string())?;
+    if let Some(max) = obj.lwm2m_version_max {
+        write_text(writer, "LWM2MVersionMax", &max.to_string())?;
+    }
+    write_text(writer, "ObjectVersion", &obj.object_version.to_string())?;
+    write_text(
+        writer,
+        "MultipleInstances",
+        multiple_instances_token(obj.has_multiple_instances),
+    )?;
+    write_text(writer, "Mandatory", mandatory_token(obj.is_mandatory))?;
+    writer.write_event(Event::Start(BytesStart::new("Resources")))?;
+    for res in &obj.resources {
+        write_resource(writer, res)?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("Resources")))?;
+    write_text(writer, "Description2", "")?;
+    write_extensions(writer, &obj.extensions)?;
+    writer.write_event(Event::End(BytesEnd::new("Object")))
+}
+
+/// Write a spec file with the given objects. The schema location is only stated if it's known.
+fn write_spec(objects: &[&Object], schema_version: Option<&str>) -> std::io::Result<String> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b'\t', 1);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut root = BytesStart::new("LWM2M");
+    if let Some(schema_version) = schema_version {
+        root.push_attribute(("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"));
+        root.push_attribute((
+            "xsi:noNamespaceSchemaLocation",
+            schema_location(schema_version).as_str(),
+        ));
+    }
+    writer.write_event(Event::Start(root))?;
+    for obj in objects {
+        write_object(&mut writer, obj)?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("LWM2M")))?;
+
+    let xml = writer.into_inner();
+    Ok(String::from_utf8(xml).expect("The written XML is valid UTF-8"))
+}
+
+impl Object {
+    /** Create a spec file (XML) that defines this object. Parsing the spec file again results in an
+        equal object, except for the source path and namespaced vendor extensions (e.g. `acme:id`),
+        which are not written because the declarations of their namespaces are not kept.
+        The descriptions of the object (`Description1` and `Description2`) are not kept when
+        parsing, so they are written empty. The position of vendor extensions isn't kept either:
+        they are written as elements after the elements of the schema, sorted by name. So the
+        elements can be in a different order than in the parsed spec file.
+    */
+    pub fn to_xml_string(&self) -> String {
+        write_spec(&[self], self.schema_version.as_deref()).expect("Writing to a Vec can't fail")
+    }
+}
+
+impl Registry {
+    /** Create a spec file (XML) that defines all objects of the registry in load order.
+        The schema location is only stated if all objects have the same schema version.
+        See `Object::to_xml_string`.
+    */
+    pub fn to_xml_string(&self) -> String {
+        let objects: Vec<&Object> = self.objects.iter().collect();
+        let schema_version = match objects.split_first() {
+            Some((first, rest))
+                if rest
+                    .iter()
+                    .all(|o| o.schema_version == first.schema_version) =>
+            {
+                first.schema_version.as_deref()
+            }
+            _ => None,
+        };
+        write_spec(&objects, schema_version).expect("Writing to a Vec can't fail")
+    }
+}
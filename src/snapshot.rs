@@ -0,0 +1,186 @@
+use crate::{LoadOptions, Object, Operations, Registry, Resource, ResourceType, Source, Version};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// The version of the snapshot format. It needs to be increased whenever the format changes.
+const FORMAT_VERSION: u8 = 5;
+
+/// Error indicating that a snapshot could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot is empty (it doesn't even contain the format version).
+    Empty,
+    /// The snapshot was created with another (incompatible) version of the format.
+    UnsupportedVersion(u8),
+    /// The snapshot data is corrupt.
+    Malformed(String),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Empty => write!(f, "Empty snapshot"),
+            SnapshotError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported snapshot format version: {}", v)
+            }
+            SnapshotError::Malformed(msg) => write!(f, "Malformed snapshot: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// A version in a snapshot: the packed major and minor version (see `Version::to_packed`) and the patch version.
+type VersionSnapshot = (u32, u16);
+
+impl From<Version> for VersionSnapshot {
+    fn from(version: Version) -> Self {
+        (version.to_packed(), version.patch)
+    }
+}
+
+impl From<VersionSnapshot> for Version {
+    fn from((packed, patch): VersionSnapshot) -> Self {
+        let version = Version::from_packed(packed);
+        Version::with_patch(version.major, version.minor, patch)
+    }
+}
+
+/// The representation of an object in a snapshot.
+#[derive(Serialize, Deserialize)]
+struct ObjectSnapshot {
+    name: String,
+    object_id: u16,
+    object_urn: Option<String>,
+    object_version: VersionSnapshot,
+    lwm2m_version: VersionSnapshot,
+    lwm2m_version_max: Option<VersionSnapshot>,
+    has_multiple_instances: bool,
+    is_mandatory: bool,
+    resources: Vec<ResourceSnapshot>,
+    source_path: Option<PathBuf>,
+    schema_version: Option<String>,
+    extensions: BTreeMap<String, String>,
+}
+
+/// The representation of a resource in a snapshot.
+#[derive(Serialize, Deserialize)]
+struct ResourceSnapshot {
+    id: u16,
+    name: String,
+    operations: u8,
+    has_multiple_instances: bool,
+    is_mandatory: bool,
+    resource_type: ResourceType,
+    description: Option<String>,
+    units: Option<String>,
+    range_enumeration: Option<String>,
+    notes: Option<String>,
+    extensions: BTreeMap<String, String>,
+}
+
+impl From<&Object> for ObjectSnapshot {
+    fn from(obj: &Object) -> Self {
+        Self {
+            name: obj.name.clone(),
+            object_id: obj.object_id,
+            object_urn: obj.object_urn.clone(),
+            object_version: obj.object_version.into(),
+            lwm2m_version: obj.lwm2m_version.into(),
+            lwm2m_version_max: obj.lwm2m_version_max.map(VersionSnapshot::from),
+            has_multiple_instances: obj.has_multiple_instances,
+            is_mandatory: obj.is_mandatory,
+            resources: obj.resources.iter().map(ResourceSnapshot::from).collect(),
+            source_path: obj.source_path.clone(),
+            schema_version: obj.schema_version.clone(),
+            extensions: obj.extensions.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl From<ObjectSnapshot> for Object {
+    fn from(obj: ObjectSnapshot) -> Self {
+        Self {
+            name: obj.name,
+            object_id: obj.object_id,
+            object_urn: obj.object_urn,
+            object_version: obj.object_version.into(),
+            lwm2m_version: obj.lwm2m_version.into(),
+            lwm2m_version_max: obj.lwm2m_version_max.map(Version::from),
+            has_multiple_instances: obj.has_multiple_instances,
+            is_mandatory: obj.is_mandatory,
+            resources: obj.resources.into_iter().map(Resource::from).collect(),
+            source_path: obj.source_path,
+            schema_version: obj.schema_version,
+            extensions: obj.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<&Resource> for ResourceSnapshot {
+    fn from(res: &Resource) -> Self {
+        Self {
+            id: res.id,
+            name: res.name.clone(),
+            operations: res.operations.bits(),
+            has_multiple_instances: res.has_multiple_instances,
+            is_mandatory: res.is_mandatory,
+            resource_type: res.resource_type,
+            description: res.description.clone(),
+            units: res.units.clone(),
+            range_enumeration: res.range_enumeration.clone(),
+            notes: res.notes.clone(),
+            extensions: res.extensions.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl From<ResourceSnapshot> for Resource {
+    fn from(res: ResourceSnapshot) -> Self {
+        let mut resource = Resource::new(
+            res.id,
+            res.name,
+            Operations::from_bits(res.operations),
+            res.has_multiple_instances,
+            res.is_mandatory,
+            res.resource_type,
+        );
+        resource.description = res.description;
+        resource.units = res.units;
+        resource.range_enumeration = res.range_enumeration;
+        resource.notes = res.notes;
+        resource.extensions = res.extensions.into_iter().collect();
+        resource
+    }
+}
+
+impl Registry {
+    /** Serialize the objects of the registry into a compact binary snapshot (e.g. for an on-disk
+        cache or for IPC). The first byte is the version of the snapshot format.
+    */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let objects: Vec<ObjectSnapshot> = self.objects.iter().map(ObjectSnapshot::from).collect();
+        let mut bytes = vec![FORMAT_VERSION];
+        bincode::serialize_into(&mut bytes, &objects).expect("Serializing into a Vec can't fail");
+        bytes
+    }
+
+    /** Create a registry from a snapshot that was created with `to_bytes`.
+        The snapshot only contains the objects, not the directories they were loaded from. So a
+        `reload` of the created registry keeps the objects.
+    */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Registry, SnapshotError> {
+        let (version, data) = bytes.split_first().ok_or(SnapshotError::Empty)?;
+        if *version != FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(*version));
+        }
+        let objects: Vec<ObjectSnapshot> =
+            bincode::deserialize(data).map_err(|e| SnapshotError::Malformed(e.to_string()))?;
+        let objects = objects.into_iter().map(Object::from).collect();
+        let mut reg = Registry::from_parts(Vec::new(), LoadOptions::default(), objects);
+        reg.source = Source::InMemory;
+        Ok(reg)
+    }
+}
@@ -1,44 +1,365 @@
-use crate::{LwM2MSpec, Object};
+use crate::{
+    extensions, Cancelled, LoadError, LoadOptions, LwM2MSpec, Object, ObjectStream, Precedence,
+    Version,
+};
+use anyhow::Context;
 use serde_xml_rs::from_str;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::str::from_utf8;
-use tokio::fs::File;
-use tokio::io::AsyncReadExt;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use walkdir::WalkDir;
 
-pub async fn load(directories: &Vec<PathBuf>) -> anyhow::Result<Vec<Object>> {
-    let mut objects = Vec::new();
-
-    for directory in directories {
-        for entry in WalkDir::new(directory) {
-            let entry = entry?;
-            if entry.file_type().is_file() {
-                let f_name = entry.path().to_string_lossy();
-
-                if f_name.ends_with(".xml") {
-                    if let Ok(file) = File::open(entry.into_path()).await {
-                        if let Ok(spec) = deserialize_spec_file(file).await {
-                            for object in spec.objects {
-                                objects.push(object);
-                            }
-                        }
+/// The maximum number of spec files that are loaded at the same time (each one needs an open file).
+const MAX_CONCURRENT_FILES: usize = 16;
+
+/// The spec files that could not be loaded together with the reason.
+pub type FileErrors = Vec<(PathBuf, LoadError)>;
+
+pub async fn load(
+    directories: &[PathBuf],
+    options: &LoadOptions,
+) -> anyhow::Result<(Vec<Object>, FileErrors)> {
+    load_cancellable(directories, options, &|| false, std::future::pending()).await
+}
+
+/** Load all spec files. The files of all directories are loaded concurrently (at most
+    `MAX_CONCURRENT_FILES` at the same time). The objects are
+    collected in a shared index that applies the directory precedence as they arrive, so the
+    result doesn't depend on the order in which the files finish loading.
+    Files that can't be loaded are skipped and reported together with the reason.
+    Loading is aborted with a `Cancelled` error as soon as `is_cancelled` returns `true` while
+    searching the directories or `cancelled` completes while loading the files. The files that are
+    still loading are aborted.
+*/
+pub async fn load_cancellable(
+    directories: &[PathBuf],
+    options: &LoadOptions,
+    is_cancelled: &dyn Fn() -> bool,
+    cancelled: impl Future<Output = ()>,
+) -> anyhow::Result<(Vec<Object>, FileErrors)> {
+    let options = Arc::new(options.clone());
+    let index = Arc::new(Mutex::new(ObjectIndex::new(options.directory_precedence)));
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_FILES));
+    let mut tasks = JoinSet::new();
+
+    for (directory_index, directory) in directories.iter().enumerate() {
+        let paths = spec_files_in(directory, &options, is_cancelled)?;
+        for (file_index, path) in paths.into_iter().enumerate() {
+            let options = options.clone();
+            let index = index.clone();
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                let permit = permits.acquire().await.expect("semaphore is never closed");
+                let result = load_spec_file(&path, &options).await;
+                drop(permit);
+                let mut index = index.lock().expect("object index poisoned");
+                index.add_result(directory_index, file_index, path, result);
+            });
+        }
+    }
+
+    tokio::pin!(cancelled);
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut cancelled => return Err(Cancelled.into()),
+            result = tasks.join_next() => match result {
+                Some(result) => result?,
+                None => break,
+            },
+        }
+    }
+    let index = std::mem::take(&mut *index.lock().expect("object index poisoned"));
+    Ok(index.into_parts())
+}
+
+/** Find all spec files within a directory. The files are sorted by name (per directory), so the load
+    order doesn't depend on the file system.
+*/
+pub(crate) fn spec_files_in(
+    directory: &Path,
+    options: &LoadOptions,
+    is_cancelled: &dyn Fn() -> bool,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in WalkDir::new(directory).sort_by_file_name() {
+        if is_cancelled() {
+            return Err(Cancelled.into());
+        }
+        let entry = entry?;
+        if entry.file_type().is_file()
+            && has_spec_extension(entry.path(), &options.extensions)
+            && !is_ignored(entry.path(), &options.ignore_files)
+        {
+            paths.push(entry.into_path());
+        }
+    }
+    Ok(paths)
+}
+
+/// The position of an object in the load order: directory, file within the directory and object within the file.
+type LoadOrder = (usize, usize, usize);
+
+/** Collects the loaded objects and applies the directory precedence if the same object (ID and
+    version) is defined in several directories. Definitions within the same directory are all kept.
+*/
+#[derive(Default)]
+pub(crate) struct ObjectIndex {
+    precedence: Precedence,
+    objects: BTreeMap<(u16, Version), Vec<(LoadOrder, Object)>>,
+    errors: Vec<(LoadOrder, PathBuf, LoadError)>,
+}
+
+impl ObjectIndex {
+    pub(crate) fn new(precedence: Precedence) -> Self {
+        Self {
+            precedence,
+            objects: BTreeMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Add the objects of a file. The files can be added in any order.
+    fn insert(&mut self, directory_index: usize, file_index: usize, objects: Vec<Object>) {
+        for (object_index, object) in objects.into_iter().enumerate() {
+            let entries = self
+                .objects
+                .entry((object.object_id, object.object_version))
+                .or_default();
+            let defined_in = entries.first().map(|((d, _, _), _)| *d);
+
+            let keep = match (self.precedence, defined_in) {
+                (Precedence::KeepAll, _) | (_, None) => true,
+                (Precedence::FirstWins, Some(d)) => {
+                    if directory_index < d {
+                        entries.clear();
                     }
+                    directory_index <= d
                 }
+                (Precedence::LastWins, Some(d)) => {
+                    if directory_index > d {
+                        entries.clear();
+                    }
+                    directory_index >= d
+                }
+            };
+            if keep {
+                entries.push(((directory_index, file_index, object_index), object));
             }
         }
     }
+
+    /// Add the result of loading a file: either its objects or the reason why it couldn't be loaded.
+    pub(crate) fn add_result(
+        &mut self,
+        directory_index: usize,
+        file_index: usize,
+        path: PathBuf,
+        result: anyhow::Result<Vec<Object>>,
+    ) {
+        match result {
+            Ok(objects) => self.insert(directory_index, file_index, objects),
+            Err(e) => self
+                .errors
+                .push(((directory_index, file_index, 0), path, e.into())),
+        }
+    }
+
+    /// Get the remaining objects and the files that couldn't be loaded, both in load order.
+    pub(crate) fn into_parts(mut self) -> (Vec<Object>, FileErrors) {
+        self.errors.sort_by_key(|(order, _, _)| *order);
+        let errors = self
+            .errors
+            .drain(..)
+            .map(|(_, path, e)| (path, e))
+            .collect();
+
+        let mut entries: Vec<(LoadOrder, Object)> = self.objects.into_values().flatten().collect();
+        entries.sort_by_key(|(order, _)| *order);
+        let objects = entries.into_iter().map(|(_, object)| object).collect();
+        (objects, errors)
+    }
+}
+
+/** Load the objects of a single spec file and set the data that is tracked per object.
+    The file is loaded on the blocking thread pool (see `read_spec_file`).
+*/
+pub async fn load_spec_file(path: &Path, options: &LoadOptions) -> anyhow::Result<Vec<Object>> {
+    let path = path.to_path_buf();
+    let options = options.clone();
+    tokio::task::spawn_blocking(move || read_spec_file(&path, &options)).await?
+}
+
+/** Load the objects of a single spec file from a reader. The name is used as the source path of
+    the objects and in the error message if the contents can't be parsed.
+*/
+pub async fn load_reader(
+    name: &str,
+    mut reader: impl AsyncRead + Unpin,
+    options: &LoadOptions,
+) -> anyhow::Result<Vec<Object>> {
+    let mut contents = vec![];
+    reader
+        .read_to_end(&mut contents)
+        .await
+        .with_context(|| format!("Failed to read spec source {}", name))?;
+    from_utf8(&contents)
+        .map_err(anyhow::Error::from)
+        .and_then(|s| load_str(s, Some(Path::new(name)), options))
+        .with_context(|| format!("Failed to parse spec source {}", name))
+}
+
+/// Load the objects of the contents of a single spec file. The source path is set to the given path.
+pub fn load_str(
+    contents: &str,
+    path: Option<&Path>,
+    options: &LoadOptions,
+) -> anyhow::Result<Vec<Object>> {
+    let spec = parse_spec(contents, options.deny_unknown_fields)?;
+    let schema_version = spec.schema_version();
+    let mut objects = spec.objects;
+    track_source(&mut objects, path, schema_version, options);
+    Ok(objects)
+}
+
+/// Load the objects of the contents of a single JSON spec file (see `json::parse`).
+pub(crate) fn load_json_str(
+    contents: &str,
+    path: &Path,
+    options: &LoadOptions,
+) -> anyhow::Result<Vec<Object>> {
+    let spec = crate::json::parse(contents, options.deny_unknown_fields)?;
+    let schema_version = spec.schema_version();
+    let mut objects = spec.objects;
+    track_source(&mut objects, Some(path), schema_version, options);
+    Ok(objects)
+}
+
+/// Check if a spec file is a JSON spec file (by its extension).
+pub(crate) fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+
+/// Set the data that is tracked per object.
+pub(crate) fn track_source(
+    objects: &mut [Object],
+    path: Option<&Path>,
+    schema_version: Option<String>,
+    options: &LoadOptions,
+) {
+    for object in objects {
+        if options.sort_resources {
+            object.resources.sort_by_key(|r| r.id);
+        }
+        object.source_path = path.map(Path::to_path_buf);
+        object.schema_version = schema_version.clone();
+    }
+}
+
+/** Load the objects of a single spec file with `std::fs` and set the data that is tracked per
+    object. This is used by both the async and the blocking loader. JSON spec files are parsed
+    with `json::parse`, large XML spec files (see `LoadOptions::streaming_threshold`) with an
+    `ObjectStream`.
+*/
+pub(crate) fn read_spec_file(path: &Path, options: &LoadOptions) -> anyhow::Result<Vec<Object>> {
+    if is_json(path) {
+        let contents = std::fs::read_to_string(path)?;
+        return load_json_str(&contents, path, options);
+    }
+    let file = std::fs::File::open(path)?;
+    let size = file.metadata()?.len();
+
+    let (mut objects, schema_version) = if options.streaming_threshold.is_some_and(|t| size > t) {
+        stream_file(file, options.deny_unknown_fields)?
+    } else {
+        let spec = deserialize_spec_file(file, options.deny_unknown_fields)?;
+        let schema_version = spec.schema_version();
+        (spec.objects, schema_version)
+    };
+    track_source(&mut objects, Some(path), schema_version, options);
     Ok(objects)
 }
 
-pub async fn deserialize_spec_file(
-    mut file: File,
-) -> Result<LwM2MSpec, Box<dyn std::error::Error>> {
+/// Load the objects and the schema version of a spec file with an `ObjectStream`.
+pub(crate) fn stream_file(
+    file: std::fs::File,
+    deny_unknown_fields: bool,
+) -> anyhow::Result<(Vec<Object>, Option<String>)> {
+    let mut stream = ObjectStream::new(std::io::BufReader::new(file))
+        .with_deny_unknown_fields(deny_unknown_fields);
+    let objects = stream.by_ref().collect::<anyhow::Result<Vec<Object>>>()?;
+    Ok((objects, stream.schema_version()))
+}
+
+fn has_spec_extension(path: &Path, extensions: &[String]) -> bool {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => extensions
+            .iter()
+            .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext)),
+        None => false,
+    }
+}
+
+fn is_ignored(path: &Path, ignore_files: &[String]) -> bool {
+    match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) => ignore_files.iter().any(|p| matches_glob(p, name)),
+        None => false,
+    }
+}
+
+/// Match a name against a simple glob pattern (`*` matches any number of characters, `?` a single one).
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+pub fn deserialize_spec_file(
+    mut file: std::fs::File,
+    deny_unknown_fields: bool,
+) -> anyhow::Result<LwM2MSpec> {
     let mut contents = vec![];
-    file.read_to_end(&mut contents).await?;
+    std::io::Read::read_to_end(&mut file, &mut contents)?;
 
     let str = from_utf8(contents.as_slice())?;
 
-    let item: LwM2MSpec = from_str(str)?;
+    parse_spec(str, deny_unknown_fields)
+}
+
+/// Parse the contents of a spec file.
+pub(crate) fn parse_spec(contents: &str, deny_unknown_fields: bool) -> anyhow::Result<LwM2MSpec> {
+    let mut item: LwM2MSpec = from_str(contents)?;
+    extensions::apply(contents, &mut item.objects, deny_unknown_fields)?;
 
     Ok(item)
 }
@@ -46,17 +367,17 @@ pub async fn deserialize_spec_file(
 #[cfg(test)]
 mod tests {
 
-    use crate::spec_files::deserialize_spec_file;
-    use crate::{Operations, Resource, ResourceType, Version};
+    use crate::spec_files::{deserialize_spec_file, matches_glob, read_spec_file};
+    use crate::{LoadError, LoadOptions, Operations, Resource, ResourceType, Version};
+    use std::fs::File;
     use std::path::PathBuf;
-    use tokio::fs::File;
 
-    #[tokio::test]
-    async fn test_parse_spec_file() -> Result<(), Box<dyn std::error::Error>> {
+    #[test]
+    fn test_parse_spec_file() -> Result<(), Box<dyn std::error::Error>> {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         d.push("tests/spec_files/lwm2m_1_2");
         d.push("0-1_2.xml");
-        let file = File::open(d).await?;
+        let file = File::open(d)?;
 
         /* checking only the first few resources */
         let expected_first_resources = vec![
@@ -67,7 +388,11 @@ mod tests {
                 false,
                 true,
                 ResourceType::String,
-            ),
+            )
+            .with_description(
+                "Uniquely identifies the LwM2M Server or LwM2M Bootstrap-Server. The format of the CoAP URI is defined in Section 6 of RFC 7252.",
+            )
+            .with_range_enumeration("0..255"),
             Resource::new(
                 1,
                 "Bootstrap-Server".to_string(),
@@ -75,6 +400,9 @@ mod tests {
                 false,
                 true,
                 ResourceType::Boolean,
+            )
+            .with_description(
+                "Determines if the current instance concerns a LwM2M Bootstrap-Server (true) or a standard LwM2M Server (false)",
             ),
             Resource::new(
                 2,
@@ -83,7 +411,11 @@ mod tests {
                 false,
                 true,
                 ResourceType::Integer,
-            ),
+            )
+            .with_description(
+                "Determines which security mode is used\n0: Pre-Shared Key mode\n1: Raw Public Key mode\n2: Certificate mode\n3: NoSec mode\n4: Certificate mode with EST",
+            )
+            .with_range_enumeration("0..4"),
             Resource::new(
                 3,
                 "Public Key or Identity".to_string(),
@@ -91,6 +423,9 @@ mod tests {
                 false,
                 true,
                 ResourceType::Opaque,
+            )
+            .with_description(
+                "Stores the LwM2M Client's certificate, public key (RPK mode) or PSK Identity (PSK mode).",
             ),
             Resource::new(
                 4,
@@ -99,6 +434,9 @@ mod tests {
                 false,
                 true,
                 ResourceType::Opaque,
+            )
+            .with_description(
+                "Stores the LwM2M Server's, respectively LwM2M Bootstrap-Server's, certificate, public key (RPK mode) or trust anchor. The Certificate Mode Resource determines the content of this resource.",
             ),
             Resource::new(
                 5,
@@ -107,15 +445,21 @@ mod tests {
                 false,
                 true,
                 ResourceType::Opaque,
+            )
+            .with_description(
+                "Stores the secret key (PSK mode) or private key (RPK or certificate mode).",
             ),
         ];
 
-        let actual = deserialize_spec_file(file).await?;
+        let actual = deserialize_spec_file(file, true)?;
         assert_eq!(actual.objects.len(), 1);
         let object = actual.objects.first().unwrap();
         assert_eq!(object.name, "LWM2M Security".to_string());
         assert_eq!(object.object_id, 0);
-        assert_eq!(object.object_urn, "urn:oma:lwm2m:oma:0:1.2".to_string());
+        assert_eq!(
+            object.object_urn,
+            Some("urn:oma:lwm2m:oma:0:1.2".to_string())
+        );
         assert_eq!(object.object_version, Version::new(1, 2));
         assert_eq!(object.lwm2m_version, Version::new(1, 1));
         assert!(object.has_multiple_instances);
@@ -129,4 +473,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_spec_file_read_error() {
+        // a directory can be opened but not read
+        let d = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/spec_files");
+        let err = read_spec_file(&d, &LoadOptions::default()).unwrap_err();
+        assert!(matches!(LoadError::from(err), LoadError::Io(_)));
+    }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("3-1_0.xml", "3-1_0.xml"));
+        assert!(!matches_glob("3-1_0.xml", "3-1_1.xml"));
+        assert!(matches_glob("*-deprecated.xml", "3-1_0-deprecated.xml"));
+        assert!(matches_glob("3-1_?.xml", "3-1_1.xml"));
+        assert!(matches_glob("*", "3-1_1.xml"));
+        assert!(matches_glob("3*1*.xml", "3-1_1.xml"));
+        assert!(!matches_glob("*.lwm2m", "3-1_1.xml"));
+        assert!(!matches_glob("3-1_?.xml", "3-1_10.xml"));
+    }
 }
@@ -0,0 +1,208 @@
+use crate::{extensions, schema_version_from_location, Object};
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+use serde_xml_rs::from_str;
+use std::io::BufRead;
+
+/** An iterator that parses the objects of a spec file one by one.
+    Only the XML of the object that is currently parsed is kept in memory. Neither the whole
+    document nor the list of all objects is buffered. This is useful for very large files that
+    contain a lot of object definitions.
+*/
+pub struct ObjectStream<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    schema_location: Option<String>,
+    deny_unknown_fields: bool,
+    done: bool,
+}
+
+impl<R: BufRead> ObjectStream<R> {
+    /// Create a new stream of objects that reads the XML from the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: Reader::from_reader(reader),
+            buf: Vec::new(),
+            schema_location: None,
+            deny_unknown_fields: false,
+            done: false,
+        }
+    }
+
+    /// Reject objects with elements or attributes that are not defined by the schema (see `LoadOptions::deny_unknown_fields`).
+    pub(crate) fn with_deny_unknown_fields(mut self, deny_unknown_fields: bool) -> Self {
+        self.deny_unknown_fields = deny_unknown_fields;
+        self
+    }
+
+    /// The location of the XML schema declared in the root element (available once it was read).
+    pub fn schema_location(&self) -> Option<&str> {
+        self.schema_location.as_deref()
+    }
+
+    /// The version of the XML schema declared in the root element (available once it was read).
+    pub fn schema_version(&self) -> Option<String> {
+        schema_version_from_location(self.schema_location.as_deref()?)
+    }
+
+    fn next_object(&mut self) -> anyhow::Result<Option<Object>> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) if e.local_name().as_ref() == b"Object" => {
+                    let mut writer = Writer::new(Vec::new());
+                    writer.write_event(Event::Start(e.into_owned()))?;
+                    self.copy_object(&mut writer)?;
+                    let xml = String::from_utf8(writer.into_inner())?;
+                    let mut object: Object = from_str(&xml)?;
+                    extensions::apply(
+                        &xml,
+                        std::slice::from_mut(&mut object),
+                        self.deny_unknown_fields,
+                    )?;
+                    return Ok(Some(object));
+                }
+                Event::Start(e) => {
+                    for attr in e.attributes() {
+                        let attr = attr?;
+                        let key = attr.key.local_name();
+                        if key.as_ref() == b"noNamespaceSchemaLocation"
+                            || key.as_ref() == b"schemaLocation"
+                        {
+                            self.schema_location = Some(attr.unescape_value()?.into_owned());
+                        }
+                    }
+                }
+                Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    /// Copy all events up to (and including) the end of the current object into the writer.
+    fn copy_object(&mut self, writer: &mut Writer<Vec<u8>>) -> anyhow::Result<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            self.buf.clear();
+            let event = self.reader.read_event_into(&mut self.buf)?;
+            match &event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                Event::Eof => anyhow::bail!("Unexpected end of file within object"),
+                _ => {}
+            }
+            writer.write_event(event)?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for ObjectStream<R> {
+    type Item = anyhow::Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.next_object();
+        if !matches!(res, Ok(Some(_))) {
+            self.done = true;
+        }
+        res.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::streaming::ObjectStream;
+    use std::io::{BufReader, Read};
+
+    /// Generates a spec file with many objects on the fly, without holding it in memory.
+    struct GeneratedSpec {
+        num_objects: u16,
+        next_object: u16,
+        pending: Vec<u8>,
+        finished: bool,
+    }
+
+    impl GeneratedSpec {
+        fn new(num_objects: u16) -> Self {
+            Self {
+                num_objects,
+                next_object: 0,
+                pending: br#"<LWM2M xsi:noNamespaceSchemaLocation="LWM2M-v1_1.xsd">"#.to_vec(),
+                finished: false,
+            }
+        }
+
+        fn object(id: u16) -> String {
+            format!(
+                r#"<Object ObjectType="MODefinition">
+                <Name>Object {id}</Name>
+                <Description1><![CDATA[Generated <object>]]></Description1>
+                <ObjectID>{id}</ObjectID>
+                <ObjectURN>urn:oma:lwm2m:x:{id}</ObjectURN>
+                <LWM2MVersion>1.1</LWM2MVersion>
+                <ObjectVersion>1.0</ObjectVersion>
+                <MultipleInstances>Multiple</MultipleInstances>
+                <Mandatory>Optional</Mandatory>
+                <Resources>
+                    <Item ID="0">
+                        <Name>Value</Name>
+                        <Operations>RW</Operations>
+                        <MultipleInstances>Single</MultipleInstances>
+                        <Mandatory>Mandatory</Mandatory>
+                        <Type>Float</Type>
+                        <RangeEnumeration/>
+                        <Units></Units>
+                        <Description>Value &amp; more</Description>
+                    </Item>
+                </Resources>
+            </Object>"#
+            )
+        }
+    }
+
+    impl Read for GeneratedSpec {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pending.is_empty() {
+                if self.next_object < self.num_objects {
+                    self.pending = Self::object(self.next_object).into_bytes();
+                    self.next_object += 1;
+                } else if !self.finished {
+                    self.pending = b"</LWM2M>".to_vec();
+                    self.finished = true;
+                } else {
+                    return Ok(0);
+                }
+            }
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_stream_many_objects() {
+        let mut stream = ObjectStream::new(BufReader::new(GeneratedSpec::new(2_000)));
+        let mut count = 0;
+        for (i, object) in stream.by_ref().enumerate() {
+            let object = object.unwrap();
+            assert_eq!(object.object_id as usize, i);
+            assert_eq!(object.resources.len(), 1);
+            assert_eq!(object.name, format!("Object {}", i));
+            count += 1;
+        }
+        assert_eq!(count, 2_000);
+        assert_eq!(stream.schema_version(), Some("1.1".to_string()));
+    }
+
+    #[test]
+    fn test_stream_truncated_file() {
+        let xml = "<LWM2M><Object><Name>Broken</Name>";
+        let mut stream = ObjectStream::new(xml.as_bytes());
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+}
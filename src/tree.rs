@@ -0,0 +1,63 @@
+use crate::{Object, Registry, ResourceType, Version};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A resource node within an `ObjectNode`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceNode {
+    /// The resource ID.
+    pub id: u16,
+    /// The name of the resource.
+    pub name: String,
+    /// The type of the resource.
+    pub resource_type: ResourceType,
+}
+
+/** An object node of the tree produced by `Registry::object_tree`.
+    The node contains all loaded versions of the object and the resources of the newest version.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectNode {
+    /// The object ID.
+    pub id: u16,
+    /// The name of the object (of the newest version).
+    pub name: String,
+    /// All loaded versions of the object in ascending order.
+    pub versions: Vec<Version>,
+    /// The resources of the newest version of the object.
+    pub resources: Vec<ResourceNode>,
+}
+
+impl Registry {
+    /** Build a nested structure of all objects and their resources, e.g. for a tree view.
+        There is one node per object ID and the nodes are sorted by object ID.
+    */
+    pub fn object_tree(&self) -> Vec<ObjectNode> {
+        let mut by_id: BTreeMap<u16, Vec<&Object>> = BTreeMap::new();
+        for obj in &self.objects {
+            by_id.entry(obj.object_id).or_default().push(obj);
+        }
+
+        by_id
+            .into_iter()
+            .filter_map(|(id, mut objs)| {
+                objs.sort_by_key(|o| o.object_version);
+                let newest = objs.last()?;
+                Some(ObjectNode {
+                    id,
+                    name: newest.name.clone(),
+                    versions: objs.iter().map(|o| o.object_version).collect(),
+                    resources: newest
+                        .resources
+                        .iter()
+                        .map(|r| ResourceNode {
+                            id: r.id,
+                            name: r.name.clone(),
+                            resource_type: r.resource_type,
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+}
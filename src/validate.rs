@@ -0,0 +1,84 @@
+use crate::{Object, Operations, Registry, ResourceType, Version};
+use std::collections::HashSet;
+
+/// An issue found when validating an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// Several resources have the same ID.
+    DuplicateResourceId(u16),
+    /// Several resources have the same name.
+    DuplicateResourceName(String),
+    /// The URN doesn't match the object ID and version.
+    UrnMismatch(String),
+    /// An executable resource has a type (executable resources must not have a type).
+    ExecutableWithType(u16),
+    /// A readable or writable resource has no type.
+    MissingType(u16),
+}
+
+impl Object {
+    /** Check the internal consistency of the object:
+        - The resource IDs and the resource names are unique.
+        - The URN (if stated) ends with the object ID and the object version.
+          The version may be omitted for version 1.0.
+        - Executable resources have no type and readable or writable resources have a type.
+    */
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let mut ids = HashSet::new();
+        let mut names = HashSet::new();
+        for res in &self.resources {
+            if !ids.insert(res.id) {
+                issues.push(ValidationIssue::DuplicateResourceId(res.id));
+            }
+            if !names.insert(&res.name) {
+                issues.push(ValidationIssue::DuplicateResourceName(res.name.clone()));
+            }
+        }
+
+        if let Some(urn) = &self.object_urn {
+            if !self.urn_matches(urn) {
+                issues.push(ValidationIssue::UrnMismatch(urn.clone()));
+            }
+        }
+
+        for res in &self.resources {
+            let has_type = res.resource_type != ResourceType::Other;
+            if res.operations == Operations::Execute && has_type {
+                issues.push(ValidationIssue::ExecutableWithType(res.id));
+            }
+            if (res.operations.is_read() || res.operations.is_write()) && !has_type {
+                issues.push(ValidationIssue::MissingType(res.id));
+            }
+        }
+
+        issues
+    }
+
+    fn urn_matches(&self, urn: &str) -> bool {
+        let id = self.object_id.to_string();
+        let mut segments = urn.trim().rsplit(':');
+        match (segments.next(), segments.next()) {
+            (Some(last), _) if last == id => self.object_version == Version::new(1, 0),
+            (Some(version), Some(last_id)) if last_id == id => {
+                version.parse::<Version>() == Ok(self.object_version)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Registry {
+    /// Validate all objects (see `Object::validate`). The issues are listed with the object ID and version.
+    pub fn validate(&self) -> Vec<(u16, Version, ValidationIssue)> {
+        self.objects
+            .iter()
+            .flat_map(|o| {
+                o.validate()
+                    .into_iter()
+                    .map(|issue| (o.object_id, o.object_version, issue))
+            })
+            .collect()
+    }
+}
@@ -1,5 +1,12 @@
+use lwm2m_registry::path::PathError;
 use lwm2m_registry::ResourceType::Opaque;
-use lwm2m_registry::{Registry, Version};
+use lwm2m_registry::{
+    diff_registries, DuplicateObjectsError, InstanceLayout, LoadError, LoadOptions,
+    MissingObjectsError, ObjectDiff, Operations, Precedence, Registry, RegistryDiff, ReloadReport,
+    Resource, ResourceChange, ResourceChangeKind, ResourceNode, ResourceType, ValidationIssue,
+    Version, VersionSelector,
+};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 async fn load_test_registry() -> Result<Registry, Box<dyn std::error::Error>> {
@@ -166,3 +173,2038 @@ async fn test_get_object_ids() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+fn fixture_dir(name: &str) -> PathBuf {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/fixtures");
+    d.push(name);
+    d
+}
+
+/// Create a temporary directory with the given number of spec files that define different objects.
+fn generated_spec_files(name: &str, count: u16) -> PathBuf {
+    let d = std::env::temp_dir().join(format!("lwm2m-registry-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&d);
+    std::fs::create_dir_all(&d).unwrap();
+    for id in 0..count {
+        let object_id = 20000 + id;
+        let spec = format!(
+            r#"<LWM2M>
+	<Object ObjectType="MODefinition">
+		<Name>Generated {object_id}</Name>
+		<ObjectID>{object_id}</ObjectID>
+		<ObjectURN>urn:oma:lwm2m:x:{object_id}</ObjectURN>
+		<LWM2MVersion>1.1</LWM2MVersion>
+		<ObjectVersion>1.0</ObjectVersion>
+		<MultipleInstances>Single</MultipleInstances>
+		<Mandatory>Optional</Mandatory>
+		<Resources>
+			<Item ID="0">
+				<Name>Value</Name>
+				<Operations>R</Operations>
+				<MultipleInstances>Single</MultipleInstances>
+				<Mandatory>Mandatory</Mandatory>
+				<Type>Integer</Type>
+			</Item>
+		</Resources>
+	</Object>
+</LWM2M>
+"#
+        );
+        std::fs::write(d.join(format!("{}.xml", object_id)), spec).unwrap();
+    }
+    d
+}
+
+#[tokio::test]
+async fn test_load_extension_case_insensitive() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("extensions")]).await?;
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_custom_extensions() -> Result<(), Box<dyn std::error::Error>> {
+    let options = LoadOptions {
+        extensions: vec!["xml".to_string(), "lwm2m".to_string()],
+        ..LoadOptions::default()
+    };
+    let registry = Registry::init_with_options(vec![fixture_dir("extensions")], options).await?;
+    let mut res = registry.get_object_ids();
+    res.sort();
+    assert_eq!(res, vec![(1, Version::new(1, 1)), (3, Version::new(1, 1))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_defining_resource() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let res = registry.objects_defining_resource(23);
+    assert_eq!(
+        res,
+        vec![
+            (0, Version::new(1, 2)),
+            (1, Version::new(1, 1)),
+            (1, Version::new(1, 2)),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_defining_resource_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let res = registry.objects_defining_resource(999);
+    assert!(res.is_empty());
+    Ok(())
+}
+
+fn resource_ids(registry: &Registry, object_id: u16, version: Version) -> Vec<u16> {
+    registry
+        .get_object_by_id(object_id, version)
+        .map(|o| o.resources.iter().map(|r| r.id).collect())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_load_sorts_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("unsorted_resources")]).await?;
+    assert_eq!(
+        resource_ids(&registry, 3, Version::new(1, 1)),
+        vec![0, 1, 2]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_keeps_file_order_of_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let options = LoadOptions {
+        sort_resources: false,
+        ..LoadOptions::default()
+    };
+    let registry =
+        Registry::init_with_options(vec![fixture_dir("unsorted_resources")], options).await?;
+    assert_eq!(
+        resource_ids(&registry, 3, Version::new(1, 1)),
+        vec![2, 0, 1]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_validate_corelink_value() {
+    let res = Resource::new(
+        0,
+        "Links".to_string(),
+        Operations::Read,
+        false,
+        true,
+        ResourceType::Corelink,
+    );
+    assert!(res.validate_value(r#"</3/0>;rt="oma.lwm2m""#));
+    assert!(!res.validate_value(r#"/3/0;rt="oma.lwm2m""#));
+}
+
+#[tokio::test]
+async fn test_validate_value() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    // Memory Total (Integer)
+    assert_eq!(
+        registry.validate_value(3, Version::new(1, 1), 21, "1024"),
+        Some(true)
+    );
+    assert_eq!(
+        registry.validate_value(3, Version::new(1, 1), 21, "a lot"),
+        Some(false)
+    );
+    // ExtDevInfo (Objlnk)
+    assert_eq!(
+        registry.validate_value(3, Version::new(1, 1), 22, "3:0"),
+        Some(true)
+    );
+    assert_eq!(
+        registry.validate_value(3, Version::new(1, 1), 99, "1"),
+        None
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_by_lwm2m_version() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let groups = registry.objects_by_lwm2m_version();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&Version::new(1, 1)].len(), 5);
+    assert_eq!(groups[&Version::new(1, 2)].len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_most_common_lwm2m_version() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    assert_eq!(
+        registry.most_common_lwm2m_version(),
+        Some(Version::new(1, 1))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_most_common_lwm2m_version_tie() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = load_test_registry().await?;
+    registry.objects.retain(|o| o.object_id == 1);
+    assert_eq!(
+        registry.most_common_lwm2m_version(),
+        Some(Version::new(1, 2))
+    );
+    Ok(())
+}
+
+fn precedence_dirs() -> Vec<PathBuf> {
+    vec![
+        fixture_dir("precedence/base"),
+        fixture_dir("precedence/vendor"),
+    ]
+}
+
+#[tokio::test]
+async fn test_directory_precedence_keep_all() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(precedence_dirs()).await?;
+    assert_eq!(registry.objects.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_directory_precedence_last_wins() -> Result<(), Box<dyn std::error::Error>> {
+    let options = LoadOptions {
+        directory_precedence: Precedence::LastWins,
+        ..LoadOptions::default()
+    };
+    let registry = Registry::init_with_options(precedence_dirs(), options).await?;
+    assert_eq!(registry.objects.len(), 1);
+    let obj = registry.get_object_by_id(3, Version::new(1, 1)).unwrap();
+    assert_eq!(obj.name, "Vendor Device");
+    assert!(obj
+        .source_path
+        .as_ref()
+        .unwrap()
+        .starts_with(fixture_dir("precedence/vendor")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_directory_precedence_first_wins() -> Result<(), Box<dyn std::error::Error>> {
+    let options = LoadOptions {
+        directory_precedence: Precedence::FirstWins,
+        ..LoadOptions::default()
+    };
+    let registry = Registry::init_with_options(precedence_dirs(), options).await?;
+    assert_eq!(registry.objects.len(), 1);
+    let name = registry.get_object_name(3, Version::new(1, 1));
+    assert_eq!(name, Some("Device".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_all_resource_names() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let names = registry.all_resource_names();
+    assert_eq!(names.iter().filter(|n| *n == "Manufacturer").count(), 1);
+    assert!(names.contains(&"Short Server ID".to_string()));
+    assert!(names.windows(2).all(|w| w[0] < w[1]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_object_urn_derived() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("no_urn")]).await?;
+    let obj = registry.get_object_by_id(3, Version::new(1, 1)).unwrap();
+    assert_eq!(obj.object_urn, None);
+    assert_eq!(obj.derived_urn(), "urn:oma:lwm2m:oma:3:1.1");
+    let res = registry.get_object_urn(3, Version::new(1, 1));
+    assert_eq!(res, Some("urn:oma:lwm2m:oma:3:1.1".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_with_resource_type() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let res: Vec<(u16, Version)> = registry
+        .objects_with_resource_type(Opaque)
+        .iter()
+        .map(|o| (o.object_id, o.object_version))
+        .collect();
+    assert_eq!(res, vec![(0, Version::new(1, 1)), (0, Version::new(1, 2))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_conditionally_mandatory_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("optional_object")]).await?;
+    let res = registry
+        .conditionally_mandatory_resources(4, Version::new(1, 1))
+        .unwrap();
+    let ids: Vec<u16> = res.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![0, 1, 2]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_conditionally_mandatory_resources_of_mandatory_object(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let res = registry.conditionally_mandatory_resources(3, Version::new(1, 1));
+    assert_eq!(res, Some(vec![]));
+    let res = registry.conditionally_mandatory_resources(99, Version::new(1, 1));
+    assert_eq!(res, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("schema_versions")]).await?;
+    let expected = HashSet::from(["1.0".to_string(), "1.1".to_string()]);
+    assert_eq!(registry.schema_versions(), expected);
+
+    let registry = load_test_registry().await?;
+    assert_eq!(
+        registry.schema_versions(),
+        HashSet::from(["1.1".to_string()])
+    );
+    Ok(())
+}
+
+#[cfg(feature = "cancellation")]
+#[tokio::test]
+async fn test_init_with_cancel() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let token = tokio_util::sync::CancellationToken::new();
+
+    let registry = Registry::init_with_cancel(vec![d.clone()], token.clone()).await?;
+    assert_eq!(6, registry.objects.len());
+
+    token.cancel();
+    let res = Registry::init_with_cancel(vec![d], token).await;
+    let err = res.err().unwrap();
+    assert_eq!(
+        err.downcast_ref::<lwm2m_registry::Cancelled>(),
+        Some(&lwm2m_registry::Cancelled)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_more_files_than_concurrently_loaded() -> Result<(), Box<dyn std::error::Error>> {
+    let d = generated_spec_files("many_files", 100);
+    let (registry, errors) =
+        Registry::init_with_report(vec![d.clone()], LoadOptions::default()).await?;
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(registry.objects.len(), 100);
+    assert!(registry.has_object_id(20099, Version::new(1, 0)));
+    std::fs::remove_dir_all(d)?;
+    Ok(())
+}
+
+#[cfg(feature = "cancellation")]
+#[tokio::test]
+async fn test_init_with_cancel_while_loading() -> Result<(), Box<dyn std::error::Error>> {
+    let d = generated_spec_files("cancel", 200);
+    let token = tokio_util::sync::CancellationToken::new();
+    let cancel = async {
+        // let the loading of the files start before cancelling
+        tokio::task::yield_now().await;
+        token.cancel();
+    };
+
+    let (res, ()) = tokio::join!(
+        Registry::init_with_cancel(vec![d.clone()], token.clone()),
+        cancel
+    );
+    let err = res.err().unwrap();
+    assert_eq!(
+        err.downcast_ref::<lwm2m_registry::Cancelled>(),
+        Some(&lwm2m_registry::Cancelled)
+    );
+    std::fs::remove_dir_all(d)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_info_map() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let map = registry.resource_info_map(VersionSelector::Newest);
+    let info = map.get("/3/0").unwrap();
+    assert_eq!(info.name, "Manufacturer");
+    assert_eq!(info.object_version, Version::new(1, 2));
+    assert_eq!(info.resource_type, ResourceType::String);
+    assert_eq!(info.operations, Operations::Read);
+    // object 0 has 31 resources in version 1.2
+    assert_eq!(map.keys().filter(|k| k.starts_with("/0/")).count(), 31);
+
+    let map = registry.resource_info_map(VersionSelector::Exact(Version::new(1, 1)));
+    assert_eq!(map["/3/0"].object_version, Version::new(1, 1));
+    assert_eq!(map.keys().filter(|k| k.starts_with("/0/")).count(), 18);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_streaming() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let options = LoadOptions {
+        streaming_threshold: Some(0),
+        ..LoadOptions::default()
+    };
+    let streamed = Registry::init_with_options(vec![d], options).await?;
+
+    assert_eq!(streamed.objects.len(), 6);
+    for obj in &registry.objects {
+        let streamed_obj = streamed
+            .get_object_by_id(obj.object_id, obj.object_version)
+            .unwrap();
+        assert_eq!(streamed_obj, obj);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_breaking_resource_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("breaking_changes")]).await?;
+    let changes = registry.breaking_resource_changes(3, Version::new(1, 0), Version::new(1, 1));
+    assert_eq!(
+        changes,
+        vec![
+            ResourceChange {
+                resource_id: 1,
+                kind: ResourceChangeKind::TypeChanged {
+                    from: ResourceType::String,
+                    to: ResourceType::Integer,
+                },
+            },
+            ResourceChange {
+                resource_id: 2,
+                kind: ResourceChangeKind::MandatoryRemoved,
+            },
+            ResourceChange {
+                resource_id: 4,
+                kind: ResourceChangeKind::OperationsNarrowed {
+                    from: Operations::ReadWrite,
+                    to: Operations::Read,
+                },
+            },
+        ]
+    );
+
+    let changes = registry.breaking_resource_changes(3, Version::new(1, 0), Version::new(2, 0));
+    assert!(changes.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_object_tree() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let tree = registry.object_tree();
+    assert_eq!(tree.len(), 3);
+
+    let device = tree.iter().find(|n| n.id == 3).unwrap();
+    assert_eq!(device.name, "Device");
+    assert_eq!(
+        device.versions,
+        vec![Version::new(1, 1), Version::new(1, 2)]
+    );
+    assert_eq!(device.resources.len(), 23);
+    assert_eq!(
+        device.resources[0],
+        ResourceNode {
+            id: 0,
+            name: "Manufacturer".to_string(),
+            resource_type: ResourceType::String,
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_resource_closest_version() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("version_fallback")]).await?;
+
+    let (version, res) = registry
+        .get_resource_closest_version(3, 4, Version::new(1, 1))
+        .unwrap();
+    assert_eq!(version, Version::new(1, 0));
+    assert_eq!(res.name, "Reboot");
+
+    let (version, _) = registry
+        .get_resource_closest_version(3, 4, Version::new(1, 2))
+        .unwrap();
+    assert_eq!(version, Version::new(1, 2));
+
+    // the resource is only defined in a newer version
+    assert!(registry
+        .get_resource_closest_version(3, 5, Version::new(1, 1))
+        .is_none());
+    // there is no version lower than the target
+    assert!(registry
+        .get_resource_closest_version(3, 0, Version::new(0, 9))
+        .is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_diff_registries() -> Result<(), Box<dyn std::error::Error>> {
+    let old = Registry::init(vec![fixture_dir("registry_diff/old")]).await?;
+    let new = Registry::init(vec![fixture_dir("registry_diff/new")]).await?;
+
+    let diff = diff_registries(&old, &new);
+    assert_eq!(diff.added, vec![(4, Version::new(1, 1))]);
+    assert!(diff.removed.is_empty());
+    assert_eq!(
+        diff.changed,
+        vec![ObjectDiff {
+            object_id: 3,
+            object_version: Version::new(1, 1),
+            metadata_changed: false,
+            added_resources: vec![2],
+            removed_resources: vec![1],
+            changed_resources: vec![0],
+        }]
+    );
+
+    let diff = diff_registries(&new, &old);
+    assert_eq!(diff.removed, vec![(4, Version::new(1, 1))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_format_valid_path() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let v = Version::new(1, 1);
+    assert_eq!(
+        registry.format_valid_path(3, v, None, None),
+        Ok("/3".to_string())
+    );
+    assert_eq!(
+        registry.format_valid_path(3, v, Some(0), None),
+        Ok("/3/0".to_string())
+    );
+    assert_eq!(
+        registry.format_valid_path(3, v, Some(0), Some(9)),
+        Ok("/3/0/9".to_string())
+    );
+    assert_eq!(
+        registry.format_valid_path(1, v, Some(2), Some(0)),
+        Ok("/1/2/0".to_string())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_format_valid_path_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let v = Version::new(1, 1);
+    assert_eq!(
+        registry.format_valid_path(99, v, None, None),
+        Err(PathError::UnknownObject(99))
+    );
+    assert_eq!(
+        registry.format_valid_path(3, v, Some(0), Some(99)),
+        Err(PathError::UnknownResource(99))
+    );
+    assert_eq!(
+        registry.format_valid_path(3, v, Some(1), None),
+        Err(PathError::InvalidInstance(1))
+    );
+    assert_eq!(
+        registry.format_valid_path(3, v, None, Some(0)),
+        Err(PathError::MissingInstance)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_resource_eq_semantic() {
+    let res = Resource::new(
+        0,
+        "Manufacturer".to_string(),
+        Operations::Read,
+        false,
+        false,
+        ResourceType::String,
+    );
+    let documented = Resource::new(
+        0,
+        "Manufacturer".to_string(),
+        Operations::Read,
+        false,
+        false,
+        ResourceType::String,
+    )
+    .with_description("Human readable manufacturer name")
+    .with_units("none");
+    assert_ne!(res, documented);
+    assert!(res.eq_semantic(&documented));
+
+    let read_write = Resource::new(
+        0,
+        "Manufacturer".to_string(),
+        Operations::ReadWrite,
+        false,
+        false,
+        ResourceType::String,
+    );
+    assert!(!res.eq_semantic(&read_write));
+}
+
+#[test]
+fn test_object_diff_range_enumeration() -> Result<(), Box<dyn std::error::Error>> {
+    let spec = |range: &str| {
+        format!(
+            r#"<LWM2M>
+	<Object ObjectType="MODefinition">
+		<Name>Sensor</Name>
+		<ObjectID>10260</ObjectID>
+		<LWM2MVersion>1.1</LWM2MVersion>
+		<ObjectVersion>1.0</ObjectVersion>
+		<MultipleInstances>Single</MultipleInstances>
+		<Mandatory>Optional</Mandatory>
+		<Resources>
+			<Item ID="0">
+				<Name>Level</Name>
+				<Operations>R</Operations>
+				<MultipleInstances>Single</MultipleInstances>
+				<Mandatory>Mandatory</Mandatory>
+				<Type>Integer</Type>
+				<RangeEnumeration>{range}</RangeEnumeration>
+			</Item>
+		</Resources>
+	</Object>
+</LWM2M>"#
+        )
+    };
+    let old = Registry::from_specs(vec![spec("0..100")])?;
+    let new = Registry::from_specs(vec![spec("0..10")])?;
+
+    let diff = old.objects[0].diff(&new.objects[0]);
+    assert_eq!(diff.changed_resources, vec![0]);
+    assert_eq!(diff_registries(&old, &new).changed, vec![diff]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = Registry::init_from_manifest(fixture_dir("manifest/index.json")).await?;
+    let mut res = registry.get_object_ids();
+    res.sort();
+    assert_eq!(res, vec![(1, Version::new(1, 2)), (3, Version::new(1, 1))]);
+
+    registry.reload().await?;
+    assert_eq!(registry.objects.len(), 2);
+
+    // the manifests are not spec files
+    let registry = Registry::init(vec![fixture_dir("manifest")]).await?;
+    assert!(registry.load_errors().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_outdated_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let res = Registry::init_from_manifest(fixture_dir("manifest/outdated.json")).await;
+    assert!(res.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_missing_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = fixture_dir("precedence/base/index.json");
+    let registry = Registry::init_from_manifest(manifest.clone()).await?;
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+
+    let options = LoadOptions {
+        manifest_fallback: false,
+        ..LoadOptions::default()
+    };
+    let res = Registry::init_from_manifest_with_options(manifest, options).await;
+    assert!(res.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_missing_manifest_with_report() -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = fixture_dir("load_errors/index.json");
+    let (mut registry, errors) =
+        Registry::init_from_manifest_with_report(manifest, LoadOptions::default()).await?;
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, fixture_dir("load_errors/4-1_0-broken.xml"));
+    assert_eq!(registry.load_errors().len(), 1);
+
+    registry.reload().await?;
+    assert_eq!(registry.load_errors().len(), 1);
+    assert_eq!(
+        registry.load_errors()[0].0,
+        fixture_dir("load_errors/4-1_0-broken.xml")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_type_distribution() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let res = registry
+        .resource_type_distribution(3, Version::new(1, 1))
+        .unwrap();
+    let expected = HashMap::from([
+        (ResourceType::String, 10),
+        (ResourceType::Integer, 8),
+        (ResourceType::Time, 1),
+        (ResourceType::ObjectLink, 1),
+        (ResourceType::Other, 3),
+    ]);
+    assert_eq!(res, expected);
+
+    assert_eq!(
+        registry.resource_type_distribution(99, Version::new(1, 1)),
+        None
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_by_resource_type() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let counts = registry.count_by_resource_type();
+    assert_eq!(counts[&ResourceType::Opaque], 10);
+    let total: usize = counts.values().sum();
+    let expected: usize = registry.objects.iter().map(|o| o.resources.len()).sum();
+    assert_eq!(total, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_object() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("invalid_object")]).await?;
+    let obj = registry.get_object_by_id(3, Version::new(1, 1)).unwrap();
+    assert_eq!(
+        obj.validate(),
+        vec![
+            ValidationIssue::DuplicateResourceId(0),
+            ValidationIssue::ExecutableWithType(4),
+        ]
+    );
+    assert_eq!(registry.validate().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_registry() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    assert_eq!(registry.validate(), vec![]);
+    Ok(())
+}
+
+#[test]
+fn test_is_reusable_common() {
+    let sensor_value = Resource::new(
+        5700,
+        "Sensor Value".to_string(),
+        Operations::Read,
+        false,
+        true,
+        ResourceType::Float,
+    );
+    assert!(sensor_value.is_reusable_common());
+    let manufacturer = Resource::new(
+        0,
+        "Manufacturer".to_string(),
+        Operations::Read,
+        false,
+        false,
+        ResourceType::String,
+    );
+    assert!(!manufacturer.is_reusable_common());
+}
+
+#[tokio::test]
+async fn test_reusable_resources_used() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = Registry::init(vec![fixture_dir("reusable_resources")]).await?;
+    assert_eq!(
+        registry.reusable_resources_used(),
+        HashSet::from([5601, 5700, 5701])
+    );
+
+    registry.set_reusable_resource_ids(HashSet::from([5700]));
+    assert_eq!(registry.reusable_resources_used(), HashSet::from([5700]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_is_reusable_resource() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = Registry::init(vec![fixture_dir("reusable_resources")]).await?;
+    assert!(registry.is_reusable_resource(5701));
+    assert!(!registry.is_reusable_resource(0));
+
+    registry.set_reusable_resource_ids(HashSet::from([5700, 1]));
+    assert!(registry.is_reusable_resource(5700));
+    assert!(registry.is_reusable_resource(1));
+    assert!(!registry.is_reusable_resource(5701));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_from_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let mut spec_files = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_files.push("tests/spec_files");
+    let dir_1_1 = spec_files.join("lwm2m_1_1");
+    let dir_1_2 = spec_files.join("lwm2m_1_2");
+    let registry = Registry::init(vec![dir_1_1, dir_1_2.clone()]).await?;
+
+    let objs = registry.objects_from_directory(&dir_1_2);
+    assert_eq!(objs.len(), 3);
+    assert!(objs.iter().all(|o| o.object_version == Version::new(1, 2)));
+
+    assert!(registry
+        .objects_from_directory(&fixture_dir("extensions"))
+        .is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_sorted_by_name() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    let names: Vec<(&str, Version)> = registry
+        .objects_sorted_by_name()
+        .iter()
+        .map(|o| (o.name.as_str(), o.object_version))
+        .collect();
+    assert_eq!(names.len(), 6);
+    assert_eq!(names[0], ("Device", Version::new(1, 1)));
+    assert_eq!(names[1], ("Device", Version::new(1, 2)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_require_objects() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    assert_eq!(registry.require_objects(&[0, 1, 3]), Ok(()));
+    let err = registry.require_objects(&[0, 2, 3]).unwrap_err();
+    assert_eq!(err, MissingObjectsError { missing: vec![2] });
+    assert_eq!(err.to_string(), "Missing required objects: 2");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_id_alias() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("resource_id_alias")]).await?;
+
+    let obj = registry.get_object_by_id(3441, Version::new(1, 0)).unwrap();
+    let ids: Vec<u16> = obj.resources.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![0, 2, 5]);
+    assert_eq!(
+        registry.get_resource_name(3441, Version::new(1, 0), 2),
+        Some("Value".to_string())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_introduced_before() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let registry = Registry::init(vec![d, fixture_dir("version_fallback")]).await?;
+
+    let ids: Vec<(u16, Version)> = registry
+        .objects_introduced_before(Version::new(1, 1))
+        .iter()
+        .map(|o| (o.object_id, o.object_version))
+        .collect();
+    assert_eq!(ids, vec![(3, Version::new(1, 0))]);
+
+    let ids: Vec<u16> = registry
+        .objects_introduced_before(Version::new(1, 2))
+        .iter()
+        .map(|o| o.object_id)
+        .collect();
+    assert_eq!(ids, vec![0, 0, 1, 3, 3, 3, 3]);
+    Ok(())
+}
+
+#[test]
+fn test_resource_type_rust_type() {
+    assert_eq!(ResourceType::String.rust_type(), "String");
+    assert_eq!(ResourceType::Integer.rust_type(), "i64");
+    assert_eq!(ResourceType::Float.rust_type(), "f64");
+    assert_eq!(ResourceType::Boolean.rust_type(), "bool");
+    assert_eq!(ResourceType::Opaque.rust_type(), "Vec<u8>");
+    assert_eq!(ResourceType::Time.rust_type(), "i64");
+    assert_eq!(ResourceType::ObjectLink.rust_type(), "(u16, u16)");
+    assert_eq!(ResourceType::UnsignedInteger.rust_type(), "u64");
+    assert_eq!(ResourceType::Corelink.rust_type(), "String");
+    assert_eq!(ResourceType::Other.rust_type(), "()");
+}
+
+#[tokio::test]
+async fn test_on_reload() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = load_test_registry().await?;
+    registry.objects.retain(|o| o.object_id != 1);
+
+    let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let captured = reports.clone();
+    registry.on_reload(move |report| captured.lock().unwrap().push(report.clone()));
+
+    registry.reload().await?;
+    assert_eq!(
+        *reports.lock().unwrap(),
+        vec![ReloadReport {
+            object_count: 6,
+            diff: RegistryDiff {
+                added: vec![(1, Version::new(1, 1)), (1, Version::new(1, 2))],
+                removed: vec![],
+                changed: vec![],
+            },
+        }]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_senml_json() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = Registry::init_from_senml_json(fixture_dir("senml/registry.json")).await?;
+
+    let mut ids = registry.get_object_ids();
+    ids.sort();
+    assert_eq!(ids, vec![(1, Version::new(1, 1)), (3, Version::new(1, 1))]);
+
+    let server = registry.get_object_by_id(1, Version::new(1, 1)).unwrap();
+    assert_eq!(server.name, "LwM2M Server");
+    assert!(server.has_multiple_instances);
+    let res_ids: Vec<u16> = server.resources.iter().map(|r| r.id).collect();
+    assert_eq!(res_ids, vec![0, 1, 8]);
+    assert_eq!(server.resources[1].units, Some("s".to_string()));
+
+    let reboot = registry
+        .get_resource_by_id(3, Version::new(1, 1), 4)
+        .unwrap();
+    assert_eq!(reboot.operations, Operations::Execute);
+    assert_eq!(reboot.resource_type, ResourceType::Other);
+    assert_eq!(
+        registry.get_resource_type(3, Version::new(1, 1), 0),
+        Some(ResourceType::String)
+    );
+    assert_eq!(
+        registry.is_resource_multi_instance(3, Version::new(1, 1), 11),
+        Some(true)
+    );
+
+    registry.reload().await?;
+    assert_eq!(registry.objects.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ignore_files() -> Result<(), Box<dyn std::error::Error>> {
+    let options = LoadOptions {
+        ignore_files: vec![
+            "3-1_0-deprecated.xml".to_string(),
+            "*-draft.xml".to_string(),
+        ],
+        ..LoadOptions::default()
+    };
+    let registry = Registry::init_with_options(vec![fixture_dir("ignore_files")], options).await?;
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+
+    let registry = Registry::init(vec![fixture_dir("ignore_files")]).await?;
+    assert_eq!(registry.objects.len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_object_version() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    assert_eq!(registry.max_object_version(0), Some(Version::new(1, 2)));
+    assert_eq!(registry.max_object_version(2), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_loading_precedence() -> Result<(), Box<dyn std::error::Error>> {
+    let dirs = vec![
+        fixture_dir("concurrent/a"),
+        fixture_dir("concurrent/b"),
+        fixture_dir("concurrent/c"),
+    ];
+    let load = |directory_precedence| {
+        let options = LoadOptions {
+            directory_precedence,
+            ..LoadOptions::default()
+        };
+        Registry::init_with_options(dirs.clone(), options)
+    };
+
+    for _ in 0..10 {
+        let registry = load(Precedence::LastWins).await?;
+        assert_eq!(registry.objects.len(), 6);
+        assert!(registry.objects.iter().all(|o| o.name.ends_with('C')));
+
+        let registry = load(Precedence::FirstWins).await?;
+        assert_eq!(registry.objects.len(), 6);
+        assert!(registry.objects.iter().all(|o| o.name.ends_with('A')));
+        // the files of a directory are loaded in the order of their names
+        let ids: Vec<u16> = registry.objects.iter().map(|o| o.object_id).collect();
+        assert_eq!(ids, vec![10, 11, 12, 13, 14, 3]);
+
+        let registry = load(Precedence::KeepAll).await?;
+        assert_eq!(registry.objects.len(), 18);
+        let suffixes: Vec<char> = registry
+            .objects
+            .iter()
+            .map(|o| o.name.chars().last().unwrap())
+            .collect();
+        assert!(suffixes.windows(2).all(|w| w[0] <= w[1]));
+        let ids: Vec<u16> = registry.objects[..6].iter().map(|o| o.object_id).collect();
+        assert_eq!(ids, vec![10, 11, 12, 13, 14, 3]);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_explain_path() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    assert_eq!(
+        registry.explain_path("/3/0/0", version),
+        "Object 3 (Device) v1.1 → Resource 0 (Manufacturer, String, Read)"
+    );
+    assert_eq!(
+        registry.explain_path("/3/0", version),
+        "Object 3 (Device) v1.1 → Instance 0"
+    );
+    assert_eq!(
+        registry.explain_path("/3", version),
+        "Object 3 (Device) v1.1"
+    );
+    assert_eq!(
+        registry.explain_path("/3/0/999", version),
+        "Object 3 (Device) v1.1 → Resource 999 is unknown"
+    );
+    assert_eq!(
+        registry.explain_path("/2/0", version),
+        "Object 2 v1.1 is unknown"
+    );
+    assert_eq!(
+        registry.explain_path("/3/x", version),
+        "Invalid path /3/x: Invalid ID in path"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vendor_extensions() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("vendor_extensions")]).await?;
+    let version = Version::new(1, 0);
+
+    assert_eq!(
+        registry.get_object_extension(10250, version, "VendorName"),
+        Some("ACME & Co")
+    );
+    assert_eq!(
+        registry.get_object_extension(10250, version, "vendor"),
+        Some("acme")
+    );
+    assert_eq!(
+        registry.get_object_extension(10250, version, "VendorInfo"),
+        None
+    );
+    assert_eq!(
+        registry.get_object_extension(10250, version, "ObjectType"),
+        None
+    );
+
+    let obj = registry.get_object_by_id(10250, version).unwrap();
+    assert_eq!(obj.extensions.len(), 2);
+    let value = &obj.resources[0];
+    assert_eq!(
+        value.extensions,
+        HashMap::from([("Precision".to_string(), "2".to_string())])
+    );
+    let label = &obj.resources[1];
+    assert_eq!(
+        label.extensions,
+        HashMap::from([("deprecated".to_string(), "true".to_string())])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_is_resource_readable_writable() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    // Manufacturer (R)
+    assert_eq!(registry.is_resource_readable(3, version, 0), Some(true));
+    assert_eq!(registry.is_resource_writable(3, version, 0), Some(false));
+    // Current Time (RW)
+    assert_eq!(registry.is_resource_readable(3, version, 13), Some(true));
+    assert_eq!(registry.is_resource_writable(3, version, 13), Some(true));
+    // Reboot (E)
+    assert_eq!(registry.is_resource_readable(3, version, 4), Some(false));
+    assert_eq!(registry.is_resource_writable(3, version, 4), Some(false));
+
+    assert_eq!(registry.is_resource_readable(3, version, 999), None);
+    assert_eq!(registry.is_resource_writable(2, version, 0), None);
+    Ok(())
+}
+
+#[cfg(feature = "snapshot")]
+#[tokio::test]
+async fn test_snapshot_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let registry = Registry::init(vec![
+        d,
+        fixture_dir("vendor_extensions"),
+        fixture_dir("patch_versions"),
+    ])
+    .await?;
+
+    let bytes = registry.to_bytes();
+    let restored = Registry::from_bytes(&bytes)?;
+    assert_eq!(restored.objects, registry.objects);
+    assert_eq!(
+        restored.get_object_extension(10250, Version::new(1, 0), "VendorName"),
+        Some("ACME & Co")
+    );
+    Ok(())
+}
+
+#[cfg(feature = "snapshot")]
+#[tokio::test]
+async fn test_snapshot_reload() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let mut restored = Registry::from_bytes(&registry.to_bytes())?;
+    restored.reload().await?;
+    assert_eq!(restored.objects.len(), 6);
+    assert_eq!(restored.objects, registry.objects);
+    Ok(())
+}
+
+#[cfg(feature = "snapshot")]
+#[tokio::test]
+async fn test_snapshot_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    use lwm2m_registry::SnapshotError;
+
+    assert_eq!(Registry::from_bytes(&[]).err(), Some(SnapshotError::Empty));
+    assert_eq!(
+        Registry::from_bytes(&[0xff, 0]).err(),
+        Some(SnapshotError::UnsupportedVersion(0xff))
+    );
+    let format_version = Registry::init(vec![]).await?.to_bytes()[0];
+    assert!(matches!(
+        Registry::from_bytes(&[format_version, 5]),
+        Err(SnapshotError::Malformed(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_type_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("type_changes")]).await?;
+
+    assert_eq!(
+        registry.resource_type_changes(3),
+        vec![
+            (
+                1,
+                Version::new(1, 0),
+                ResourceType::String,
+                Version::new(1, 1),
+                ResourceType::Integer
+            ),
+            (
+                5,
+                Version::new(1, 1),
+                ResourceType::String,
+                Version::new(1, 2),
+                ResourceType::Integer
+            ),
+        ]
+    );
+    assert!(registry.resource_type_changes(1).is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deny_unknown_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("unknown_fields")]).await?;
+    assert!(registry.has_object_id(3, Version::new(1, 1)));
+
+    let strict = || LoadOptions {
+        deny_unknown_fields: true,
+        ..LoadOptions::default()
+    };
+    let registry =
+        Registry::init_with_options(vec![fixture_dir("unknown_fields")], strict()).await?;
+    assert!(registry.objects.is_empty());
+
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let registry = Registry::init_with_options(vec![d], strict()).await?;
+    assert_eq!(registry.objects.len(), 6);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_common_object_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let registry = Registry::init(vec![d, fixture_dir("version_fallback")]).await?;
+
+    assert_eq!(
+        registry.common_object_ids(Version::new(1, 0), Version::new(1, 1)),
+        vec![3]
+    );
+    assert_eq!(
+        registry.common_object_ids(Version::new(1, 1), Version::new(1, 2)),
+        vec![1]
+    );
+    assert!(registry
+        .common_object_ids(Version::new(1, 0), Version::new(2, 0))
+        .is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_resource_notes() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("resource_notes")]).await?;
+    let version = Version::new(1, 1);
+
+    assert_eq!(
+        registry.get_resource_notes(3, version, 13),
+        Some("Reported in UTC.")
+    );
+    assert_eq!(registry.get_resource_notes(3, version, 0), None);
+    assert_eq!(registry.get_resource_notes(3, version, 99), None);
+    let res = registry.get_resource_by_id(3, version, 13).unwrap();
+    assert!(res.extensions.is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "docs")]
+#[tokio::test]
+async fn test_to_markdown() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let markdown = registry.to_markdown();
+
+    assert!(markdown.contains("## Device (ID 3, version 1.1)\n"));
+    assert!(markdown.contains(
+        "| ID | Name | Operations | Type | Mandatory | Multiple Instances | Units | Description |"
+    ));
+    assert!(markdown.contains(
+        "| 0 | Manufacturer | R | String | No | No |  | Human readable manufacturer name |"
+    ));
+    assert_eq!(markdown.matches("\n## ").count() + 1, 6);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_instance_layout() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    let layout = registry.instance_layout(3, Version::new(1, 1)).unwrap();
+    assert_eq!(
+        layout,
+        InstanceLayout {
+            mandatory: vec![4, 11, 16],
+            optional: vec![0, 1, 2, 3, 5, 6, 7, 8, 9, 10, 12, 13, 14, 15, 17, 18, 19, 20, 21, 22],
+            multi_instance: vec![6, 7, 8, 11, 22],
+        }
+    );
+    assert_eq!(registry.instance_layout(2, Version::new(1, 1)), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_without_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("empty_resources")]).await?;
+    let version = Version::new(1, 0);
+
+    for object_id in [10260, 10261] {
+        assert_eq!(registry.get_resources(object_id, version), Some(&[][..]));
+        assert_eq!(registry.resource_count(object_id, version), Some(0));
+        assert_eq!(registry.max_resource_id(object_id, version), None);
+        assert!(registry.get_resource_by_id(object_id, version, 0).is_none());
+        assert_eq!(registry.get_resource_name(object_id, version, 0), None);
+        assert_eq!(
+            registry.conditionally_mandatory_resources(object_id, version),
+            Some(vec![])
+        );
+        assert_eq!(
+            registry.instance_layout(object_id, version),
+            Some(InstanceLayout::default())
+        );
+        assert_eq!(
+            registry.resource_type_distribution(object_id, version),
+            Some(HashMap::new())
+        );
+    }
+    assert_eq!(registry.get_resources(2, version), None);
+    assert_eq!(registry.resource_count(2, version), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_objects_by_urn_suffix() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    let objs = registry.find_objects_by_urn_suffix("3:1.1");
+    assert_eq!(objs.len(), 1);
+    assert_eq!(objs[0].name, "Device");
+    assert_eq!(objs[0].urn(), "urn:oma:lwm2m:oma:3:1.1");
+
+    assert_eq!(registry.find_objects_by_urn_suffix(":1.1").len(), 3);
+    assert_eq!(registry.find_objects_by_urn_suffix("1.1").len(), 3);
+    assert!(registry.find_objects_by_urn_suffix("13:1.1").is_empty());
+    assert!(registry.find_objects_by_urn_suffix(".1").is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_with_object_name() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    let (object_name, res) = registry.resource_with_object_name(3, version, 0).unwrap();
+    assert_eq!(object_name, "Device");
+    assert_eq!(res.name, "Manufacturer");
+    assert!(registry
+        .resource_with_object_name(3, version, 999)
+        .is_none());
+    assert!(registry.resource_with_object_name(2, version, 0).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_registry_query() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    let resources: Vec<(u16, Version, u16)> = registry
+        .query()
+        .object_id(3)
+        .min_version(Version::new(1, 2))
+        .mandatory(true)
+        .resources()
+        .iter()
+        .map(|(o, r)| (o.object_id, o.object_version, r.id))
+        .collect();
+    assert_eq!(
+        resources,
+        vec![
+            (3, Version::new(1, 2), 4),
+            (3, Version::new(1, 2), 11),
+            (3, Version::new(1, 2), 16),
+        ]
+    );
+
+    let query = registry
+        .query()
+        .mandatory(true)
+        .resource_type(ResourceType::Integer);
+    let resources: Vec<(u16, u16)> = query
+        .clone()
+        .object_id(3)
+        .resources()
+        .iter()
+        .map(|(o, r)| (o.object_id, r.id))
+        .collect();
+    assert_eq!(resources, vec![(3, 11), (3, 11)]);
+
+    let objects: Vec<u16> = query.objects().iter().map(|o| o.object_id).collect();
+    assert!(objects.contains(&3));
+    assert!(registry
+        .query()
+        .object_id(3)
+        .resource_type(ResourceType::Opaque)
+        .objects()
+        .is_empty());
+    assert_eq!(registry.query().objects().len(), 6);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_version_count() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+
+    assert_eq!(registry.version_count(0), 2);
+    assert_eq!(registry.version_count(2), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_with_report() -> Result<(), Box<dyn std::error::Error>> {
+    let (registry, errors) =
+        Registry::init_with_report(vec![fixture_dir("load_errors")], LoadOptions::default())
+            .await?;
+
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+    assert_eq!(errors.len(), 1);
+    let (path, error) = &errors[0];
+    assert_eq!(path, &fixture_dir("load_errors/4-1_0-broken.xml"));
+    assert!(matches!(error, LoadError::Parse(_)));
+
+    let registry = Registry::init(vec![fixture_dir("load_errors")]).await?;
+    assert_eq!(registry.objects.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_units_and_range() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    assert_eq!(registry.get_resource_units(3, version, 9), Some("%"));
+    assert_eq!(registry.get_resource_units(3, version, 0), None);
+    assert_eq!(registry.get_resource_units(3, version, 999), None);
+
+    let battery_level = registry.get_resource_by_id(3, version, 9).unwrap();
+    assert_eq!(battery_level.range_enumeration, Some("0..100".to_string()));
+    assert!(battery_level
+        .description
+        .as_ref()
+        .unwrap()
+        .starts_with("Contains the current battery level"));
+    let manufacturer = registry.get_resource_by_id(3, version, 0).unwrap();
+    assert_eq!(manufacturer.range_enumeration, None);
+
+    let res = Resource::new(
+        9,
+        "Battery Level".to_string(),
+        Operations::Read,
+        false,
+        false,
+        ResourceType::Integer,
+    )
+    .with_units("%")
+    .with_range_enumeration("0..100");
+    assert!(res.eq_semantic(battery_level));
+    assert_eq!(res.units, battery_level.units);
+    assert_eq!(res.range_enumeration, battery_level.range_enumeration);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_object_supports_lwm2m() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("lwm2m_version_range")]).await?;
+    let version = Version::new(1, 1);
+
+    let device = registry.get_object_by_id(3, version).unwrap();
+    assert_eq!(device.lwm2m_version, Version::new(1, 0));
+    assert_eq!(device.lwm2m_version_max, Some(Version::new(1, 1)));
+    assert_eq!(
+        registry.object_supports_lwm2m(3, version, Version::new(1, 0)),
+        Some(true)
+    );
+    assert_eq!(
+        registry.object_supports_lwm2m(3, version, Version::new(1, 1)),
+        Some(true)
+    );
+    assert_eq!(
+        registry.object_supports_lwm2m(3, version, Version::new(1, 2)),
+        Some(false)
+    );
+
+    let server = registry.get_object_by_id(1, version).unwrap();
+    assert_eq!(server.lwm2m_version_max, None);
+    assert_eq!(
+        registry.object_supports_lwm2m(1, version, Version::new(1, 0)),
+        Some(false)
+    );
+    assert_eq!(
+        registry.object_supports_lwm2m(1, version, Version::new(2, 0)),
+        Some(true)
+    );
+    assert_eq!(registry.object_supports_lwm2m(2, version, version), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_count_by_operation() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    let counts = registry.resource_count_by_operation(3, version).unwrap();
+    assert_eq!(
+        counts.values().sum::<usize>(),
+        registry.resource_count(3, version).unwrap()
+    );
+    assert_eq!(counts.get(&Operations::Read), Some(&17));
+    assert_eq!(counts.get(&Operations::ReadWrite), Some(&3));
+    assert_eq!(counts.get(&Operations::Execute), Some(&3));
+    assert_eq!(counts.get(&Operations::Write), None);
+
+    assert!(registry.resource_count_by_operation(2, version).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_object_by_id_newest() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let device = registry.get_object_by_id_newest(3).unwrap();
+    assert_eq!(device.object_version, Version::new(1, 2));
+    assert_eq!(
+        registry
+            .get_resource_by_id_newest(3, 0)
+            .map(|r| r.name.as_str()),
+        Some("Manufacturer")
+    );
+    assert!(registry.get_resource_by_id_newest(3, 999).is_none());
+    assert!(registry.get_object_by_id_newest(2).is_none());
+    assert!(registry.get_resource_by_id_newest(2, 0).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_object_by_id_newest_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("newest_duplicates")]).await?;
+    // both 3-1_1-a.xml and 3-1_1-b.xml define version 1.1, the lower path wins
+    let device = registry.get_object_by_id_newest(3).unwrap();
+    assert_eq!(device.object_version, Version::new(1, 1));
+    assert_eq!(device.name, "Device A");
+    assert!(registry.get_resource_by_id_newest(3, 1).is_some());
+    assert_eq!(
+        registry.get_object_id_by_name_newest("Device"),
+        Some((3, Version::new(1, 0)))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_sources() -> Result<(), Box<dyn std::error::Error>> {
+    let spec_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/spec_files/lwm2m_1_1");
+    let device = std::fs::read(spec_dir.join("3-1_1.xml"))?;
+    let server = std::fs::read(spec_dir.join("1-1_1.xml"))?;
+    let sources: Vec<(String, Box<dyn tokio::io::AsyncRead + Unpin>)> = vec![
+        (
+            "db://specs/3".to_string(),
+            Box::new(std::io::Cursor::new(device)),
+        ),
+        (
+            "db://specs/1".to_string(),
+            Box::new(std::io::Cursor::new(server)),
+        ),
+    ];
+
+    let mut registry = Registry::init_from_sources(sources).await?;
+    let version = Version::new(1, 1);
+    assert_eq!(registry.objects.len(), 2);
+    assert_eq!(
+        registry.get_object_name(3, version),
+        Some("Device".to_string())
+    );
+    assert_eq!(
+        registry.get_object_by_id(1, version).unwrap().source_path,
+        Some(PathBuf::from("db://specs/1"))
+    );
+
+    registry.reload().await?;
+    assert_eq!(registry.objects.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_from_sources_error() {
+    let sources: Vec<(String, Box<dyn tokio::io::AsyncRead + Unpin>)> =
+        vec![("broken-blob".to_string(), Box::new(&b"<LWM2M><Object>"[..]))];
+    let err = Registry::init_from_sources(sources).await.err().unwrap();
+    assert!(err.to_string().contains("broken-blob"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_load_patch_version() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("patch_versions")]).await?;
+    assert_eq!(registry.objects.len(), 2);
+    assert!(registry.has_object_id(10270, Version::with_patch(1, 0, 1)));
+    assert_eq!(
+        registry.get_object_id_by_name_newest("Vendor Sensor"),
+        Some((10270, Version::with_patch(1, 0, 1)))
+    );
+    let obj = registry
+        .get_object_by_id(10270, Version::with_patch(1, 0, 1))
+        .unwrap();
+    assert_eq!(obj.derived_urn(), "urn:oma:lwm2m:x:10270:1.0.1");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_objects_with_executable_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let objs = registry.objects_with_executable_resources();
+    let ids: Vec<(u16, Version)> = objs
+        .iter()
+        .map(|o| (o.object_id, o.object_version))
+        .collect();
+    assert!(ids.contains(&(3, Version::new(1, 1))));
+    assert!(ids.contains(&(3, Version::new(1, 2))));
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    // the Security object has no executable resources
+    assert!(!ids.iter().any(|(id, _)| *id == 0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_from_specs() -> Result<(), Box<dyn std::error::Error>> {
+    let spec_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/spec_files/lwm2m_1_1");
+    let device = std::fs::read_to_string(spec_dir.join("3-1_1.xml"))?;
+    let server = std::fs::read_to_string(spec_dir.join("1-1_1.xml"))?;
+    let security = std::fs::read_to_string(spec_dir.join("0-1_1.xml"))?;
+
+    let mut registry = Registry::from_specs(vec![device, server])?;
+    let version = Version::new(1, 1);
+    assert_eq!(registry.objects.len(), 2);
+    assert_eq!(
+        registry.get_object_name(3, version),
+        Some("Device".to_string())
+    );
+    assert_eq!(
+        registry.get_object_by_id(3, version).unwrap().source_path,
+        None
+    );
+
+    registry.add_spec_str(&security)?;
+    assert_eq!(registry.objects.len(), 3);
+    assert!(registry.has_object_id(0, version));
+    assert!(registry.add_spec_str("<LWM2M><Object>").is_err());
+    assert_eq!(registry.objects.len(), 3);
+
+    // in-memory registries keep their objects on reload
+    registry.reload().await?;
+    assert_eq!(registry.objects.len(), 3);
+
+    // the objects are identical to the ones loaded from the files
+    let from_files = Registry::init(vec![spec_dir]).await?;
+    for obj in &from_files.objects {
+        let in_memory = registry
+            .get_object_by_id(obj.object_id, obj.object_version)
+            .unwrap();
+        assert_eq!(in_memory.resources, obj.resources);
+        assert_eq!(in_memory.schema_version, obj.schema_version);
+    }
+
+    assert!(Registry::from_specs(vec!["no xml".to_string()]).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "blocking")]
+#[tokio::test]
+async fn test_init_blocking() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let directories = vec![d, fixture_dir("load_errors")];
+
+    let mut registry = Registry::init_blocking(directories.clone())?;
+    let expected = Registry::init(directories).await?;
+    assert_eq!(registry.objects, expected.objects);
+
+    assert_eq!(registry.load_errors(), expected.load_errors());
+
+    registry.reload_blocking()?;
+    assert_eq!(registry.objects, expected.objects);
+    assert_eq!(registry.load_errors().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_ids_in_range() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    // the Device object defines the resources 0 to 22
+    assert_eq!(
+        registry.resource_ids_in_range(3, version, 20, 30),
+        Some(vec![20, 21, 22])
+    );
+    assert_eq!(
+        registry.resource_ids_in_range(3, version, 0, 5),
+        Some(vec![0, 1, 2, 3, 4, 5])
+    );
+    assert_eq!(
+        registry.resource_ids_in_range(3, version, 100, 200),
+        Some(vec![])
+    );
+    assert_eq!(
+        registry.resource_ids_in_range(3, version, 5, 0),
+        Some(vec![])
+    );
+    assert_eq!(registry.resource_ids_in_range(2, version, 0, 5), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    assert!(registry.find_duplicates().is_empty());
+    assert_eq!(
+        registry.get_unique_object_ids().len(),
+        registry.get_object_ids().len()
+    );
+
+    // 3-1_1-a.xml and 3-1_1-b.xml both define the Device object in version 1.1
+    let registry = Registry::init(vec![fixture_dir("newest_duplicates")]).await?;
+    assert_eq!(registry.find_duplicates(), vec![(3, Version::new(1, 1))]);
+    assert_eq!(registry.get_object_ids().len(), 3);
+    assert_eq!(
+        registry.get_unique_object_ids(),
+        vec![(3, Version::new(1, 0)), (3, Version::new(1, 1))]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_strict() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init_strict(vec![fixture_dir("patch_versions")]).await?;
+    assert_eq!(registry.objects.len(), 2);
+
+    let err = Registry::init_strict(vec![fixture_dir("newest_duplicates")])
+        .await
+        .err()
+        .unwrap();
+    let err = err.downcast::<DuplicateObjectsError>()?;
+    assert_eq!(err.duplicates, vec![(3, Version::new(1, 1))]);
+    assert_eq!(
+        err.to_string(),
+        "Duplicate object definitions: 3 (version 1.1)"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_alias() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+    let alias = "urn:acme:lwm2m:device:1.1";
+
+    assert!(registry.get_object_by_urn(alias).is_none());
+    assert_eq!(
+        registry
+            .get_object_by_urn("urn:oma:lwm2m:oma:3:1.1")
+            .map(|o| (o.object_id, o.object_version)),
+        Some((3, version))
+    );
+
+    registry.add_alias(alias, (3, version));
+    let obj = registry.get_object_by_urn(alias).unwrap();
+    assert_eq!((obj.object_id, obj.object_version), (3, version));
+    assert_eq!(
+        registry.get_object_id_by_name_newest(alias),
+        Some((3, version))
+    );
+
+    // the objects of the registry take precedence over aliases
+    registry.add_alias("Device", (3, version));
+    assert_eq!(
+        registry.get_object_id_by_name_newest("Device"),
+        Some((3, Version::new(1, 2)))
+    );
+
+    // aliases to unknown objects are not resolved
+    registry.add_alias("urn:acme:lwm2m:missing", (2, version));
+    assert!(registry
+        .get_object_by_urn("urn:acme:lwm2m:missing")
+        .is_none());
+
+    registry.reload().await?;
+    assert!(registry.get_object_by_urn(alias).is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_to_xml_string_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let registry = Registry::init(vec![
+        d,
+        fixture_dir("vendor_extensions"),
+        fixture_dir("resource_notes"),
+        fixture_dir("lwm2m_version_range"),
+        fixture_dir("patch_versions"),
+        fixture_dir("no_urn"),
+    ])
+    .await?;
+
+    for obj in &registry.objects {
+        let xml = obj.to_xml_string();
+        let mut parsed = Registry::from_specs(vec![xml.clone()])?.objects;
+        assert_eq!(parsed.len(), 1, "{}", xml);
+        let mut parsed = parsed.remove(0);
+        assert_eq!(parsed.source_path, None);
+        parsed.source_path = obj.source_path.clone();
+        assert_eq!(&parsed, obj, "{}", xml);
+    }
+
+    let parsed = Registry::from_specs(vec![registry.to_xml_string()])?;
+    assert_eq!(parsed.objects.len(), registry.objects.len());
+    for (parsed, obj) in parsed.objects.iter().zip(&registry.objects) {
+        assert_eq!(parsed.resources, obj.resources);
+        assert_eq!(parsed.extensions, obj.extensions);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_to_xml_string_schema_elements() -> Result<(), Box<dyn std::error::Error>> {
+    let spec = r#"<LWM2M xmlns:acme="http://example.com/acme">
+	<Object ObjectType="MODefinition" acme:id="7" vendor="acme">
+		<Name>Vendor Sensor</Name>
+		<ObjectID>10250</ObjectID>
+		<LWM2MVersion>1.1</LWM2MVersion>
+		<ObjectVersion>1.0</ObjectVersion>
+		<MultipleInstances>Single</MultipleInstances>
+		<Mandatory>Optional</Mandatory>
+		<Resources>
+			<Item ID="0" acme:unit="mm">
+				<Name>Value</Name>
+				<Operations>R</Operations>
+				<MultipleInstances>Single</MultipleInstances>
+				<Mandatory>Mandatory</Mandatory>
+				<Type>Float</Type>
+			</Item>
+		</Resources>
+	</Object>
+</LWM2M>"#;
+    let registry = Registry::from_specs(vec![spec.to_string()])?;
+    let xml = registry.objects[0].to_xml_string();
+    assert!(xml.contains("<Description1></Description1>"), "{}", xml);
+    assert!(xml.contains("<Description2></Description2>"), "{}", xml);
+    assert!(xml.contains("<ObjectURN></ObjectURN>"), "{}", xml);
+    // the namespace of the prefixed attributes is unknown
+    assert!(!xml.contains("acme:"), "{}", xml);
+
+    let parsed = Registry::from_specs(vec![xml])?;
+    let obj = &parsed.objects[0];
+    assert_eq!(obj.object_urn, None);
+    assert_eq!(
+        obj.extensions,
+        HashMap::from([("vendor".to_string(), "acme".to_string())])
+    );
+    assert!(obj.resources[0].extensions.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = Registry::init(vec![fixture_dir("load_errors")]).await?;
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+    assert_eq!(registry.load_errors().len(), 1);
+    let (path, error) = &registry.load_errors()[0];
+    assert_eq!(path, &fixture_dir("load_errors/4-1_0-broken.xml"));
+    assert!(!error.is_empty());
+
+    registry.reload().await?;
+    assert_eq!(registry.load_errors().len(), 1);
+
+    let registry = load_test_registry().await?;
+    assert!(registry.load_errors().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_operations() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("invalid_operations")]).await?;
+    assert_eq!(registry.get_object_ids(), vec![(3, Version::new(1, 1))]);
+    let (path, error) = &registry.load_errors()[0];
+    assert_eq!(path, &fixture_dir("invalid_operations/10280-1_0.xml"));
+    assert!(error.contains("Read"), "{}", error);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_objects_for_lwm2m_version() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Registry::init(vec![fixture_dir("lwm2m_versions")]).await?;
+
+    // the object 10290 has a low object version, but requires LwM2M 1.2
+    assert_eq!(
+        registry.get_object_ids_for_lwm2m_version(Version::new(1, 1)),
+        vec![(10291, Version::new(2, 0)), (10292, Version::new(1, 0))]
+    );
+    assert_eq!(
+        registry.get_object_ids_for_lwm2m_version(Version::new(1, 0)),
+        vec![(10291, Version::new(2, 0))]
+    );
+    assert_eq!(
+        registry
+            .get_objects_for_lwm2m_version(Version::new(1, 2))
+            .len(),
+        3
+    );
+    assert!(registry
+        .get_objects_for_lwm2m_version(Version::new(0, 9))
+        .is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_mandatory_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    let resources = registry.get_resources(3, version).unwrap();
+    assert_eq!(resources.len(), 23);
+    assert_eq!(resources[0].name, "Manufacturer");
+
+    let mandatory: Vec<u16> = registry
+        .get_mandatory_resources(3, version)
+        .unwrap()
+        .iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(mandatory, vec![4, 11, 16]);
+    assert_eq!(registry.get_mandatory_resources(2, version), None);
+
+    let empty = Registry::init(vec![fixture_dir("empty_resources")]).await?;
+    assert_eq!(
+        empty.get_mandatory_resources(10260, Version::new(1, 0)),
+        Some(vec![])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_resources_by_operation() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = load_test_registry().await?;
+    let version = Version::new(1, 1);
+
+    let executable: Vec<&str> = registry
+        .get_resources_by_operation(3, version, Operations::Execute)
+        .unwrap()
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(executable.len(), 3);
+    assert!(executable.contains(&"Reboot"));
+    assert!(executable.contains(&"Factory Reset"));
+    assert_eq!(
+        registry
+            .get_resources_by_operation(3, version, Operations::ReadWrite)
+            .map(|r| r.len()),
+        Some(3)
+    );
+    assert_eq!(
+        registry.get_resources_by_operation(3, version, Operations::Write),
+        Some(vec![])
+    );
+    assert_eq!(
+        registry.get_resources_by_operation(2, version, Operations::Read),
+        None
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolve_object_link() -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/spec_files");
+    let registry = Registry::init(vec![d, fixture_dir("object_links")]).await?;
+    let version = Version::new(1, 0);
+
+    // the target is stated in the description
+    let apn = registry.resolve_object_link(10300, version, 0).unwrap();
+    assert_eq!(
+        (apn.object_id, apn.name.as_str()),
+        (11, "APN connection profile")
+    );
+    // the target is the range/enumeration, the newest version is resolved
+    let device = registry.resolve_object_link(10300, version, 1).unwrap();
+    assert_eq!(
+        (device.object_id, device.object_version),
+        (3, Version::new(1, 2))
+    );
+    // the target isn't documented
+    assert!(registry.resolve_object_link(10300, version, 2).is_none());
+    // the target isn't loaded
+    assert!(registry.resolve_object_link(10300, version, 3).is_none());
+    // not an object link
+    assert!(registry.resolve_object_link(10300, version, 4).is_none());
+    assert!(registry.resolve_object_link(10300, version, 99).is_none());
+    assert!(registry.resolve_object_link(2, version, 0).is_none());
+
+    // the LwM2M Server object documents the APN link target in the description
+    let apn_link = registry
+        .get_resource_by_id(1, Version::new(1, 1), 10)
+        .unwrap();
+    assert_eq!(apn_link.object_link_target(), Some(11));
+    assert!(registry
+        .resolve_object_link(1, Version::new(1, 1), 10)
+        .is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_json_spec_file() -> Result<(), Box<dyn std::error::Error>> {
+    // JSON spec files are only loaded if enabled
+    let registry = Registry::init(vec![fixture_dir("json_specs")]).await?;
+    assert_eq!(registry.objects.len(), 1);
+
+    let options = LoadOptions {
+        extensions: vec!["xml".to_string(), "json".to_string()],
+        ..LoadOptions::default()
+    };
+    let registry = Registry::init_with_options(vec![fixture_dir("json_specs")], options).await?;
+    assert_eq!(registry.objects.len(), 2);
+    assert!(registry.load_errors().is_empty());
+
+    let (mut json, xml): (Vec<_>, Vec<_>) = registry.objects.iter().partition(|o| {
+        o.source_path
+            .as_ref()
+            .is_some_and(|p| p.extension().unwrap() == "json")
+    });
+    let (json, xml) = (json.remove(0), xml[0]);
+    assert_eq!(json.name, xml.name);
+    assert_eq!(json.object_id, xml.object_id);
+    assert_eq!(json.object_urn, xml.object_urn);
+    assert_eq!(json.object_version, xml.object_version);
+    assert_eq!(json.lwm2m_version, xml.lwm2m_version);
+    assert_eq!(json.has_multiple_instances, xml.has_multiple_instances);
+    assert_eq!(json.is_mandatory, xml.is_mandatory);
+    assert_eq!(json.resources, xml.resources);
+    assert_eq!(json.schema_version, xml.schema_version);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_json_spec_file_unknown_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let options = LoadOptions {
+        extensions: vec!["json".to_string()],
+        ..LoadOptions::default()
+    };
+    let dirs = vec![fixture_dir("json_unknown_fields")];
+    let registry = Registry::init_with_options(dirs.clone(), options.clone()).await?;
+    let version = Version::new(1, 0);
+    assert_eq!(
+        registry.get_object_extension(10311, version, "VendorName"),
+        Some("ACME")
+    );
+    // only scalars are captured
+    assert_eq!(
+        registry.get_object_extension(10311, version, "VendorInfo"),
+        None
+    );
+    let reading = registry.get_resource_by_id(10311, version, 0).unwrap();
+    assert_eq!(
+        reading.extensions.get("Precision").map(String::as_str),
+        Some("2")
+    );
+
+    let options = LoadOptions {
+        deny_unknown_fields: true,
+        ..options
+    };
+    let (registry, errors) = Registry::init_with_report(dirs, options).await?;
+    assert!(registry.objects.is_empty());
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0].1, LoadError::Parse(msg) if msg.contains("Unknown field")));
+    Ok(())
+}
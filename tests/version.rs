@@ -0,0 +1,84 @@
+use lwm2m_registry::Version;
+use std::str::FromStr;
+
+#[test]
+fn test_is_compatible_with() {
+    assert!(Version::new(1, 1).is_compatible_with(&Version::new(1, 2)));
+    assert!(Version::new(1, 2).is_compatible_with(&Version::new(1, 0)));
+    assert!(Version::new(2, 0).is_compatible_with(&Version::new(2, 0)));
+}
+
+#[test]
+fn test_is_not_compatible_with() {
+    assert!(!Version::new(1, 1).is_compatible_with(&Version::new(2, 0)));
+    assert!(!Version::new(2, 0).is_compatible_with(&Version::new(1, 2)));
+}
+
+#[test]
+fn test_packed_round_trip() {
+    let version = Version::new(1, 2);
+    assert_eq!(version.to_packed(), 0x0001_0002);
+    assert_eq!(Version::from_packed(version.to_packed()), version);
+
+    let version = Version::new(u16::MAX, u16::MAX);
+    assert_eq!(Version::from_packed(version.to_packed()), version);
+
+    // the patch version is not packed
+    let version = Version::with_patch(1, 0, 1);
+    assert_eq!(version.to_packed(), Version::new(1, 0).to_packed());
+}
+
+#[test]
+fn test_packed_order() {
+    assert!(Version::new(1, 10).to_packed() < Version::new(2, 0).to_packed());
+}
+
+#[test]
+fn test_version_from_str_with_patch() {
+    assert_eq!(Version::from_str("1.0.1"), Ok(Version::with_patch(1, 0, 1)));
+    assert_eq!(Version::from_str("1.1"), Ok(Version::with_patch(1, 1, 0)));
+    assert_eq!(Version::from_str("2"), Ok(Version::new(2, 0)));
+    assert!(Version::from_str("1.0.1.2").is_err());
+    assert!(Version::from_str("1.0.x").is_err());
+    assert!(Version::from_str("").is_err());
+
+    assert!(Version::with_patch(1, 0, 1) > Version::new(1, 0));
+    assert!(Version::with_patch(1, 0, 9) < Version::new(1, 1));
+}
+
+#[test]
+fn test_version_parse_with_minor_explicit() {
+    let (implicit, implicit_minor) = Version::parse_with_minor_explicit("1").unwrap();
+    let (explicit, explicit_minor) = Version::parse_with_minor_explicit("1.0").unwrap();
+    assert!(!implicit_minor);
+    assert!(explicit_minor);
+    assert_eq!(implicit, explicit);
+    assert_eq!(
+        Version::parse_with_minor_explicit("1.0.1"),
+        Ok((Version::with_patch(1, 0, 1), true))
+    );
+    assert!(Version::parse_with_minor_explicit("1.x").is_err());
+}
+
+#[test]
+fn test_version_display() {
+    assert_eq!(Version::new(1, 1).to_string(), "1.1");
+    assert_eq!(Version::new(2, 0).to_string(), "2.0");
+    assert_eq!(Version::with_patch(1, 0, 1).to_string(), "1.0.1");
+    assert_eq!(format!("v{}", Version::new(1, 2)), "v1.2");
+
+    for version in [
+        Version::new(0, 0),
+        Version::new(1, 10),
+        Version::with_patch(3, 2, 1),
+        Version::new(u16::MAX, u16::MAX),
+    ] {
+        assert_eq!(Version::from_str(&version.to_string()), Ok(version));
+    }
+
+    let version = Version::with_patch(1, 2, 3);
+    assert_eq!(
+        (version.major(), version.minor(), version.patch()),
+        (1, 2, 3)
+    );
+}